@@ -12,13 +12,17 @@ use anyhow::Result;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
 
 // ═══════════════════════════════════════════════════════════════════════════════════
 // SECTION: Core Data Structures
 // ═══════════════════════════════════════════════════════════════════════════════════
 
 /// Different types of content that can be in a prompt.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PromptSection {
     /// The main goal or objective
     Goal(String),
@@ -28,14 +32,41 @@ pub enum PromptSection {
     Step(String),
     /// Desired output format
     Output(String),
+    /// A rule the response must respect
+    Constraint(String),
+    /// Something the response must not do; rendered grouped under one label
+    Avoid(String),
+    /// A sample response whose tone and style the model should match
+    ToneExample(String),
+    /// Background information or reference material
+    Context(String),
 }
 
 /// A prompt containing multiple sections.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Prompt {
     sections: Vec<PromptSection>,
+    /// Placeholder names that must be filled before the prompt is sent
+    #[serde(default)]
+    required_slots: Vec<String>,
 }
 
+/// Filler words removed by `Prompt::compress`.
+pub const DEFAULT_STOPWORDS: &[&str] = &[
+    "a",
+    "an",
+    "the",
+    "please",
+    "kindly",
+    "just",
+    "very",
+    "really",
+    "basically",
+    "actually",
+    "simply",
+    "quite",
+];
+
 impl Default for Prompt {
     fn default() -> Self {
         Self::new()
@@ -47,273 +78,2838 @@ impl Prompt {
     pub const fn new() -> Self {
         Self {
             sections: Vec::new(),
+            required_slots: Vec::new(),
         }
     }
 
     fn add_section(&mut self, section: PromptSection) {
         self.sections.push(section);
     }
-}
 
-impl fmt::Display for Prompt {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let mut result = Vec::new();
+    /// Replaces the existing section of the same kind in place, or appends it.
+    fn replace_section(&mut self, section: PromptSection) {
+        let kind = std::mem::discriminant(&section);
+        match self
+            .sections
+            .iter()
+            .position(|s| std::mem::discriminant(s) == kind)
+        {
+            Some(index) => self.sections[index] = section,
+            None => self.sections.push(section),
+        }
+    }
+
+    /// Required slots whose `{name}` placeholder still appears in the prompt.
+    #[must_use]
+    pub fn unfilled_slots(&self) -> Vec<&str> {
+        self.required_slots
+            .iter()
+            .filter(|name| {
+                let placeholder = format!("{{{name}}}");
+                self.sections
+                    .iter()
+                    .any(|s| s.content().contains(&placeholder))
+            })
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Replaces every `{name}` placeholder with `value`.
+    #[must_use]
+    pub fn fill_slot(&self, name: &str, value: &str) -> Self {
+        let placeholder = format!("{{{name}}}");
+        self.map_sections(|s| s.with_content(s.content().replace(&placeholder, value)))
+    }
+
+    /// Names of every `{identifier}` placeholder in the prompt, in order of
+    /// first appearance.
+    fn placeholders(&self) -> Vec<&str> {
+        let mut names = Vec::new();
         for section in &self.sections {
-            match section {
-                PromptSection::Goal(content) => {
-                    result.push(format!("Goal: {content}"));
-                }
-                PromptSection::Role(content) => {
-                    result.push(format!("Role: {content}"));
-                }
-                PromptSection::Step(content) => {
-                    result.push(format!("Step: {content}"));
-                }
-                PromptSection::Output(content) => {
-                    result.push(format!("Output: {content}"));
+            let mut rest = section.content();
+            while let Some(start) = rest.find('{') {
+                rest = &rest[start + 1..];
+                let Some(end) = rest.find('}') else { break };
+                let name = &rest[..end];
+                let is_identifier = name
+                    .chars()
+                    .next()
+                    .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+                    && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+                if is_identifier && !names.contains(&name) {
+                    names.push(name);
                 }
             }
         }
-        write!(f, "{}", result.join("\n"))
+        names
     }
-}
 
-// ═══════════════════════════════════════════════════════════════════════════════════
-// SECTION: Builder Pattern
-// ═══════════════════════════════════════════════════════════════════════════════════
+    /// Fills the given slots and renders the prompt, failing if any required
+    /// slot is still unfilled.
+    ///
+    /// Other `{word}` text is left as written; use `render_with_strict` to
+    /// reject it.
+    pub fn render_with(&self, values: &[(&str, &str)]) -> Result<String> {
+        let filled = values.iter().fold(self.clone(), |prompt, (name, value)| {
+            prompt.fill_slot(name, value)
+        });
+        let unfilled = filled.unfilled_slots();
+        if !unfilled.is_empty() {
+            anyhow::bail!("unfilled required slots: {}", unfilled.join(", "));
+        }
+        Ok(filled.to_string())
+    }
 
-/// Builder for creating prompts using a fluent API.
-#[derive(Default)]
-pub struct PromptBuilder {
-    prompt: Prompt,
-}
+    /// Like `render_with`, but also fails if a required slot never appears as a
+    /// placeholder or if any `{identifier}` placeholder is left without a value,
+    /// so a misspelled slot name is caught instead of rendered verbatim.
+    pub fn render_with_strict(&self, values: &[(&str, &str)]) -> Result<String> {
+        let placeholders = self.placeholders();
+        let missing: Vec<&str> = self
+            .required_slots
+            .iter()
+            .map(String::as_str)
+            .filter(|name| !placeholders.contains(name))
+            .collect();
+        if !missing.is_empty() {
+            anyhow::bail!("required slots not used in prompt: {}", missing.join(", "));
+        }
 
-impl PromptBuilder {
+        let unknown: Vec<&str> = placeholders
+            .into_iter()
+            .filter(|name| !values.iter().any(|(value_name, _)| value_name == name))
+            .filter(|name| !self.required_slots.iter().any(|slot| slot == name))
+            .collect();
+        if !unknown.is_empty() {
+            anyhow::bail!("unfilled placeholders: {}", unknown.join(", "));
+        }
+        self.render_with(values)
+    }
+
+    /// Rough token estimate using `HeuristicTokenizer`.
     #[must_use]
-    pub const fn new() -> Self {
+    pub fn estimated_tokens(&self) -> usize {
+        self.estimated_tokens_with(&HeuristicTokenizer)
+    }
+
+    /// Token count of the rendered prompt according to `tokenizer`.
+    #[must_use]
+    pub fn estimated_tokens_with(&self, tokenizer: &dyn Tokenizer) -> usize {
+        tokenizer.count_tokens(&self.to_string())
+    }
+
+    /// One-line description for logs: the goal (or first section), truncated to
+    /// `max_len` characters. A trailing `…` marks any dropped text, including
+    /// lines after the first. Blank content yields `(empty prompt)` untruncated.
+    #[must_use]
+    pub fn summary(&self, max_len: usize) -> String {
+        let Some(text) = self
+            .sections
+            .iter()
+            .find(|s| matches!(s, PromptSection::Goal(_)))
+            .or_else(|| self.sections.first())
+            .map(|s| s.content().trim())
+            .filter(|text| !text.is_empty())
+        else {
+            return "(empty prompt)".to_string();
+        };
+        let line = text.lines().next().unwrap_or_default().trim();
+        let dropped_lines = line.len() < text.len();
+
+        let len = line.chars().count();
+        if len <= max_len && !dropped_lines {
+            return line.to_string();
+        }
+        let mut truncated: String = line.chars().take(max_len.saturating_sub(1)).collect();
+        if max_len > 0 {
+            truncated.push('…');
+        }
+        truncated
+    }
+
+    /// Describes the prompt as an OpenAI tool definition (`"type": "function"`).
+    ///
+    /// The name is derived from the goal, and the parameters come from the
+    /// Output section when it holds a JSON schema object; otherwise a single
+    /// `result` string parameter described by the Output text is used.
+    #[must_use]
+    pub fn to_openai_function_call(&self) -> serde_json::Value {
+        let goal = self.first_content(|s| matches!(s, PromptSection::Goal(_)));
+        let output = self.first_content(|s| matches!(s, PromptSection::Output(_)));
+
+        let parameters = match output {
+            Some(text) => match serde_json::from_str::<serde_json::Value>(text) {
+                Ok(schema) if schema.is_object() => schema,
+                _ => serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "result": { "type": "string", "description": text }
+                    },
+                    "required": ["result"]
+                }),
+            },
+            None => serde_json::json!({ "type": "object", "properties": {} }),
+        };
+
+        serde_json::json!({
+            "type": "function",
+            "function": {
+                "name": function_name(goal.unwrap_or_default()),
+                "description": goal.unwrap_or_default(),
+                "parameters": parameters
+            }
+        })
+    }
+
+    fn first_content(&self, kind: impl Fn(&PromptSection) -> bool) -> Option<&str> {
+        self.sections
+            .iter()
+            .find(|s| kind(s))
+            .map(PromptSection::content)
+    }
+
+    /// Strips `DEFAULT_STOPWORDS` from every section to save tokens.
+    #[must_use]
+    pub fn compress(&self) -> Self {
+        self.compress_with(DEFAULT_STOPWORDS)
+    }
+
+    /// Strips the given filler words from every section.
+    ///
+    /// Only bare all-lowercase words are removed, so capitalized words (likely
+    /// proper nouns or acronyms), numbers, and words carrying punctuation such as
+    /// `a)` or `a.` are always kept. Use `token_savings` to see how much was saved.
+    #[must_use]
+    pub fn compress_with(&self, stopwords: &[&str]) -> Self {
+        let compress_line = |line: &str| {
+            line.split_whitespace()
+                .filter(|word| {
+                    !(word.chars().all(|c| c.is_ascii_lowercase())
+                        && stopwords.iter().any(|stop| stop.eq_ignore_ascii_case(word)))
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+
+        self.map_sections(|section| {
+            let content = section
+                .content()
+                .lines()
+                .map(compress_line)
+                .collect::<Vec<_>>()
+                .join("\n");
+            section.with_content(content)
+        })
+    }
+
+    /// Returns a copy of this prompt with every section transformed by `f`.
+    fn map_sections(&self, f: impl FnMut(&PromptSection) -> PromptSection) -> Self {
         Self {
-            prompt: Prompt::new(),
+            sections: self.sections.iter().map(f).collect(),
+            ..self.clone()
         }
     }
 
-    /// Adds a goal section
+    /// Estimated tokens saved by `other` relative to this prompt.
     #[must_use]
-    pub fn goal(mut self, goal: impl Into<String>) -> Self {
-        self.prompt.add_section(PromptSection::Goal(goal.into()));
-        self
+    pub fn token_savings(&self, other: &Self) -> usize {
+        self.estimated_tokens()
+            .saturating_sub(other.estimated_tokens())
     }
 
-    /// Adds a role section
+    /// Drops trailing sections until the estimated token count fits `max_tokens`.
     #[must_use]
-    pub fn role(mut self, role: impl Into<String>) -> Self {
-        self.prompt.add_section(PromptSection::Role(role.into()));
-        self
+    pub fn truncate_to(&self, max_tokens: usize) -> Self {
+        let mut truncated = self.clone();
+        while truncated.estimated_tokens() > max_tokens && truncated.sections.pop().is_some() {}
+        truncated
     }
 
-    /// Adds a step section
+    /// Deterministic JSON form of the prompt: object keys are sorted and
+    /// there is no insignificant whitespace.
+    ///
+    /// The output is byte-identical across runs and platforms for equal
+    /// prompts, which makes it safe to hash for caching and audit checksums.
     #[must_use]
-    pub fn step(mut self, step: impl Into<String>) -> Self {
-        self.prompt.add_section(PromptSection::Step(step.into()));
-        self
+    pub fn canonicalize(&self) -> String {
+        // `serde_json::Value` keeps object keys in a sorted map, so converting
+        // through it fixes the field order regardless of how fields are declared
+        serde_json::to_value(self)
+            .expect("prompts only contain strings")
+            .to_string()
     }
 
-    /// Adds an output format section
+    /// Stable hex checksum of `canonicalize`.
     #[must_use]
-    pub fn output(mut self, output: impl Into<String>) -> Self {
-        self.prompt
-            .add_section(PromptSection::Output(output.into()));
-        self
+    pub fn checksum(&self) -> String {
+        format!("{:016x}", fnv1a_64(self.canonicalize().as_bytes()))
     }
 
-    /// Finishes building and returns the prompt
+    /// Compares prompts section by section, ignoring case and differences in
+    /// whitespace. Section kinds and order must still match.
     #[must_use]
-    pub fn build(self) -> Prompt {
-        self.prompt
+    pub fn is_equivalent_to(&self, other: &Self) -> bool {
+        let normalize = |text: &str| {
+            text.split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ")
+                .to_lowercase()
+        };
+
+        self.sections.len() == other.sections.len()
+            && self.sections.iter().zip(&other.sections).all(|(a, b)| {
+                std::mem::discriminant(a) == std::mem::discriminant(b)
+                    && normalize(a.content()) == normalize(b.content())
+            })
+    }
+
+    /// Reorders sections into the canonical sequence role → goal → context →
+    /// steps → constraints → avoid → tone examples → output, keeping the
+    /// relative order within each kind.
+    #[must_use]
+    pub fn normalize_order(&self) -> Self {
+        let mut normalized = self.clone();
+        normalized
+            .sections
+            .sort_by_key(PromptSection::canonical_rank);
+        normalized
+    }
+
+    /// Scores how demanding this prompt is, from 0.0 (trivial) to 1.0 (complex).
+    ///
+    /// Combines section count, estimated length, and whether the prompt carries
+    /// structured guidance (steps, constraints and an output format). Useful for
+    /// routing complex prompts to a stronger model.
+    #[must_use]
+    pub fn complexity_score(&self) -> f64 {
+        const MAX_SECTIONS: f64 = 10.0;
+        const MAX_TOKENS: f64 = 500.0;
+
+        let sections = (self.sections.len() as f64 / MAX_SECTIONS).min(1.0);
+        let tokens = (self.estimated_tokens() as f64 / MAX_TOKENS).min(1.0);
+        let guidance: [fn(&PromptSection) -> bool; 3] = [
+            |s| matches!(s, PromptSection::Step(_)),
+            |s| matches!(s, PromptSection::Constraint(_)),
+            |s| matches!(s, PromptSection::Output(_)),
+        ];
+        let present = guidance
+            .iter()
+            .filter(|kind| self.sections.iter().any(kind))
+            .count();
+        let structure = present as f64 / guidance.len() as f64;
+
+        0.4 * sections + 0.4 * tokens + 0.2 * structure
     }
 }
 
-// ═══════════════════════════════════════════════════════════════════════════════════
-// SECTION: LLM Client Interface
-// ═══════════════════════════════════════════════════════════════════════════════════
+/// Turns free text into a snake_case identifier accepted as an OpenAI function name.
+fn function_name(text: &str) -> String {
+    let name = text
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_ascii_lowercase)
+        .collect::<Vec<_>>()
+        .join("_");
+    let name: String = name.chars().take(64).collect();
+    let name = name.trim_end_matches('_');
+    if name.is_empty() {
+        "prompt".to_string()
+    } else {
+        name.to_string()
+    }
+}
 
-/// Simple interface for communicating with LLMs.
-#[async_trait]
-pub trait SimpleLLMClient: Send + Sync {
-    /// Sends a prompt to the LLM and gets a response.
-    async fn generate(&self, prompt: &str) -> Result<String>;
+impl fmt::Display for Prompt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_string_with_labels(&LabelSet::default()))
+    }
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════════
-// SECTION: Mock LLM Client
+// SECTION: Token Counting
 // ═══════════════════════════════════════════════════════════════════════════════════
 
-/// Mock LLM client for demonstration and testing.
-pub struct MockLLMClient;
+/// Counts tokens in text; implement it to plug in a model-specific tokenizer.
+pub trait Tokenizer {
+    fn count_tokens(&self, text: &str) -> usize;
+}
 
-#[async_trait]
-impl SimpleLLMClient for MockLLMClient {
-    /// Returns a mock response based on prompt content.
-    async fn generate(&self, prompt: &str) -> Result<String> {
-        // Simulate network delay
-        tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+/// Model-agnostic estimate using the common ~4 characters per token heuristic.
+pub struct HeuristicTokenizer;
 
-        // Simple responses based on banking prompt content
-        if prompt.contains("credit risk") || prompt.contains("Credit Risk") {
-            Ok("CREDIT ANALYSIS COMPLETE\n\nApplicant Profile: FICO 720, DTI 28%, Stable Employment\nRisk Assessment: LOW RISK (2.1% default probability)\nRecommendation: APPROVED at Prime + 1.25%\nRequired: Income verification, property appraisal".to_string())
-        } else if prompt.contains("fraud") || prompt.contains("Fraud") {
-            Ok("FRAUD ALERT ISSUED\n\nTransaction Pattern: Multiple ATM withdrawals detected\nRisk Level: HIGH (Score 85/100)\nGeographic Anomaly: 500+ miles from normal location\nAction Required: FREEZE card, contact customer immediately".to_string())
-        } else {
-            Ok("Analysis complete. Banking task processed according to regulatory guidelines and best practices.".to_string())
-        }
+impl Tokenizer for HeuristicTokenizer {
+    fn count_tokens(&self, text: &str) -> usize {
+        text.chars().count().div_ceil(4)
     }
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════════
-// SECTION: Banking Templates
+// SECTION: Rendering Labels
 // ═══════════════════════════════════════════════════════════════════════════════════
 
-/// Pre-built templates for common banking use cases.
-#[derive(Debug, Clone)]
-pub enum BankingTemplate {
-    /// Credit risk assessment and loan evaluation
-    CreditRisk { loan_type: String, focus: String },
-    /// Fraud detection and prevention
-    FraudDetection { channel: String, scope: String },
+/// Labels printed in front of each section kind when rendering a prompt.
+///
+/// The default is English (`Goal:`, `Role:`, ...); supply a custom set for
+/// localized deployments.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LabelSet {
+    pub goal: String,
+    pub role: String,
+    pub step: String,
+    pub output: String,
+    pub constraint: String,
+    pub avoid: String,
+    /// Marker in front of each item in the grouped `Avoid` block
+    pub avoid_item: String,
+    pub tone_example: String,
+    pub context: String,
 }
 
-impl BankingTemplate {
-    /// Creates a pre-configured prompt builder.
+impl Default for LabelSet {
+    fn default() -> Self {
+        Self::english()
+    }
+}
+
+impl LabelSet {
+    /// The built-in English labels used by `Display`.
     #[must_use]
-    pub fn to_builder(&self) -> PromptBuilder {
+    pub fn english() -> Self {
+        Self {
+            goal: "Goal".to_string(),
+            role: "Role".to_string(),
+            step: "Step".to_string(),
+            output: "Output".to_string(),
+            constraint: "Constraint".to_string(),
+            avoid: "Avoid".to_string(),
+            avoid_item: "- Do not: ".to_string(),
+            tone_example: "Tone Example".to_string(),
+            context: "Context".to_string(),
+        }
+    }
+
+    /// Returns the label for the given section's kind.
+    #[must_use]
+    pub fn label_for(&self, section: &PromptSection) -> &str {
+        match section {
+            PromptSection::Goal(_) => &self.goal,
+            PromptSection::Role(_) => &self.role,
+            PromptSection::Step(_) => &self.step,
+            PromptSection::Output(_) => &self.output,
+            PromptSection::Constraint(_) => &self.constraint,
+            PromptSection::Avoid(_) => &self.avoid,
+            PromptSection::ToneExample(_) => &self.tone_example,
+            PromptSection::Context(_) => &self.context,
+        }
+    }
+}
+
+impl PromptSection {
+    /// Returns the text content of this section.
+    #[must_use]
+    pub fn content(&self) -> &str {
         match self {
-            Self::CreditRisk { loan_type, focus } => PromptBuilder::new()
-                .goal(format!(
-                    "Assess credit risk for {loan_type} focusing on {focus}"
-                ))
-                .role("Senior Credit Risk Analyst")
-                .step("Analyze credit history and payment patterns")
-                .step("Evaluate income stability and debt ratios")
-                .step("Calculate default probability and risk rating")
-                .step("Determine loan terms and interest rates")
-                .output("Risk assessment with approval recommendation"),
-            Self::FraudDetection { channel, scope } => PromptBuilder::new()
-                .goal(format!("Detect fraud in {channel} using {scope}"))
-                .role("Fraud Detection Specialist")
-                .step("Analyze transaction patterns and anomalies")
-                .step("Apply fraud scoring models")
-                .step("Check against known risk indicators")
-                .step("Generate alerts and recommended actions")
-                .output("Fraud risk assessment with action plan"),
+            Self::Goal(content)
+            | Self::Role(content)
+            | Self::Step(content)
+            | Self::Output(content)
+            | Self::Constraint(content)
+            | Self::Avoid(content)
+            | Self::ToneExample(content)
+            | Self::Context(content) => content,
         }
     }
+}
 
-    /// Gets a description of what this template does.
+impl PromptSection {
+    /// Position of this section's kind in `Prompt::normalize_order`.
+    const fn canonical_rank(&self) -> u8 {
+        match self {
+            Self::Role(_) => 0,
+            Self::Goal(_) => 1,
+            Self::Context(_) => 2,
+            Self::Step(_) => 3,
+            Self::Constraint(_) => 4,
+            Self::Avoid(_) => 5,
+            Self::ToneExample(_) => 6,
+            Self::Output(_) => 7,
+        }
+    }
+
+    /// Explains what this kind of section is for, as used by `Prompt::explain`.
     #[must_use]
-    pub fn description(&self) -> String {
+    pub const fn purpose(&self) -> &'static str {
         match self {
-            Self::CreditRisk { loan_type, focus } => {
-                format!("Assesses credit risk for {loan_type} focusing on {focus}")
-            }
-            Self::FraudDetection { channel, scope } => {
-                format!("Detects fraud in {channel} using {scope}")
-            }
+            Self::Goal(_) => "Goal — defines the objective the model optimizes for",
+            Self::Role(_) => "Role — sets the persona and expertise the model answers with",
+            Self::Step(_) => "Step — breaks the task into an ordered instruction to follow",
+            Self::Output(_) => "Output — specifies the shape and format of the response",
+            Self::Constraint(_) => "Constraint — sets a rule the response must never break",
+            Self::Avoid(_) => "Avoid — names a behavior the model must steer clear of",
+            Self::ToneExample(_) => "Tone Example — anchors the voice and style of the response",
+            Self::Context(_) => "Context — supplies background the model should rely on",
+        }
+    }
+
+    /// Returns a section of the same kind holding `content`.
+    #[must_use]
+    pub fn with_content(&self, content: impl Into<String>) -> Self {
+        let content = content.into();
+        match self {
+            Self::Goal(_) => Self::Goal(content),
+            Self::Role(_) => Self::Role(content),
+            Self::Step(_) => Self::Step(content),
+            Self::Output(_) => Self::Output(content),
+            Self::Constraint(_) => Self::Constraint(content),
+            Self::Avoid(_) => Self::Avoid(content),
+            Self::ToneExample(_) => Self::ToneExample(content),
+            Self::Context(_) => Self::Context(content),
         }
     }
 }
 
-// ═══════════════════════════════════════════════════════════════════════════════════
-// SECTION: Demo Function
-// ═══════════════════════════════════════════════════════════════════════════════════
+/// How `Prompt::parse_with` treats lines that don't start with a known label.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseMode {
+    /// Treat them as continuation lines of the previous section
+    Lenient,
+    /// Reject them with an error
+    Strict,
+}
 
-/// Demonstrates the banking prompt library.
-async fn demo_banking_prompts() -> Result<()> {
-    println!("🏦 Simple Banking Prompt Library Demo");
-    println!("=====================================");
-    println!();
+impl Prompt {
+    /// Parses text rendered by `Display` back into sections.
+    ///
+    /// Unlabelled lines are appended to the previous section, which keeps
+    /// multi-line section content intact on a round trip.
+    pub fn parse(text: &str) -> Result<Self> {
+        Self::parse_with(text, &LabelSet::default(), ParseMode::Lenient)
+    }
 
-    // Manual prompt building
-    println!("📝 Manual Prompt Building:");
-    let manual_prompt = PromptBuilder::new()
-        .goal("Evaluate loan application")
-        .role("Credit Analyst")
-        .step("Review credit score and history")
-        .step("Analyze income and debt ratios")
-        .output("Approval recommendation with terms")
-        .build();
+    /// Parses text rendered with `labels`, handling unlabelled lines per `mode`.
+    pub fn parse_with(text: &str, labels: &LabelSet, mode: ParseMode) -> Result<Self> {
+        let kinds = [
+            PromptSection::Goal(String::new()),
+            PromptSection::Role(String::new()),
+            PromptSection::Step(String::new()),
+            PromptSection::Output(String::new()),
+            PromptSection::Constraint(String::new()),
+            PromptSection::ToneExample(String::new()),
+            PromptSection::Context(String::new()),
+        ];
 
-    println!(
-        "✅ Built manually: {} sections",
-        manual_prompt.sections.len()
-    );
-    println!();
+        let mut prompt = Self::new();
+        let mut in_avoid_group = false;
+        for (number, line) in text.lines().enumerate() {
+            if line.strip_suffix(':') == Some(labels.avoid.as_str()) {
+                in_avoid_group = true;
+                continue;
+            }
+            if let Some(item) = line
+                .strip_prefix(labels.avoid_item.as_str())
+                .filter(|_| in_avoid_group)
+            {
+                prompt.add_section(PromptSection::Avoid(item.to_string()));
+                continue;
+            }
 
-    // Template-based building
-    println!("🎯 Template-Based Building:");
-    let template = BankingTemplate::CreditRisk {
-        loan_type: "mortgage".to_string(),
-        focus: "default risk".to_string(),
-    };
+            let labelled = kinds.iter().find_map(|kind| {
+                line.strip_prefix(labels.label_for(kind))
+                    .and_then(|rest| rest.strip_prefix(':'))
+                    .map(|rest| kind.with_content(rest.strip_prefix(' ').unwrap_or(rest)))
+            });
 
-    let template_prompt = template.to_builder().build();
-    println!("✅ {}", template.description());
-    println!(
-        "✅ Built from template: {} sections",
-        template_prompt.sections.len()
-    );
-    println!();
+            match (labelled, prompt.sections.last_mut(), mode) {
+                (Some(section), _, _) => {
+                    in_avoid_group = false;
+                    prompt.add_section(section);
+                }
+                (None, Some(previous), ParseMode::Lenient) => {
+                    *previous = previous.with_content(format!("{}\n{line}", previous.content()));
+                }
+                (None, _, _) => {
+                    anyhow::bail!("line {}: unrecognized prompt line: {line:?}", number + 1)
+                }
+            }
+        }
+        Ok(prompt)
+    }
 
-    // Test with LLM client
-    println!("🤖 Testing with LLM:");
-    let llm_client = MockLLMClient;
+    /// Renders the prompt like `Display`, but using the given section labels.
+    #[must_use]
+    pub fn to_string_with_labels(&self, labels: &LabelSet) -> String {
+        self.rendered_blocks(labels).join("\n")
+    }
 
-    let response = llm_client.generate(&template_prompt.to_string()).await?;
-    println!("💬 Response:");
-    println!("{response}");
-    println!();
+    /// Renders the prompt with `separator` between rendered blocks instead of a
+    /// single newline.
+    ///
+    /// All `Avoid` sections render as one block, so they share a single slot
+    /// between separators. The separator never follows the last block.
+    #[must_use]
+    pub fn to_string_with_separator(&self, separator: &str) -> String {
+        self.rendered_blocks(&LabelSet::default()).join(separator)
+    }
 
-    println!("🎉 Demo completed!");
-    Ok(())
+    /// Renders each section as `Label: content`, except that all `Avoid`
+    /// sections are gathered into a single block where the first one appears.
+    fn rendered_blocks(&self, labels: &LabelSet) -> Vec<String> {
+        let mut blocks = Vec::new();
+        let mut avoid_rendered = false;
+        for section in &self.sections {
+            match section {
+                PromptSection::Avoid(_) if avoid_rendered => {}
+                PromptSection::Avoid(_) => {
+                    avoid_rendered = true;
+                    let items: Vec<String> = self
+                        .sections
+                        .iter()
+                        .filter(|s| matches!(s, PromptSection::Avoid(_)))
+                        .map(|s| format!("{}{}", labels.avoid_item, s.content()))
+                        .collect();
+                    blocks.push(format!("{}:\n{}", labels.avoid, items.join("\n")));
+                }
+                _ => blocks.push(format!(
+                    "{}: {}",
+                    labels.label_for(section),
+                    section.content()
+                )),
+            }
+        }
+        blocks
+    }
+
+    /// Renders the prompt with an annotation under each section describing its purpose.
+    #[must_use]
+    pub fn explain(&self) -> String {
+        let labels = LabelSet::default();
+        self.sections
+            .iter()
+            .map(|section| {
+                format!(
+                    "{}: {}\n    ↳ {}",
+                    labels.label_for(section),
+                    section.content(),
+                    section.purpose()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Renders the prompt with each output line prefixed by its 1-based number.
+    #[must_use]
+    pub fn to_numbered_string(&self) -> String {
+        self.to_string()
+            .lines()
+            .enumerate()
+            .map(|(index, line)| format!("{}: {line}", index + 1))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════════
-// SECTION: Main Function
+// SECTION: Prompt Versioning
 // ═══════════════════════════════════════════════════════════════════════════════════
 
-#[tokio::main]
-async fn main() -> Result<()> {
-    demo_banking_prompts().await?;
+/// A single section-level difference between two prompt versions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SectionChange {
+    /// Present only in the newer version
+    Added(PromptSection),
+    /// Present only in the older version
+    Removed(PromptSection),
+}
 
-    println!();
-    println!("📚 Key Learning Points:");
-    println!("   ✅ Builder pattern for fluent APIs");
-    println!("   ✅ Trait abstraction for LLM clients");
-    println!("   ✅ Template system for reusable prompts");
-    println!("   ✅ Async programming with Rust");
+impl Prompt {
+    /// Lists sections removed from and added to this prompt to obtain `newer`.
+    ///
+    /// Sections are compared by kind and content; an edited section shows up
+    /// as a removal followed by an addition.
+    #[must_use]
+    pub fn diff(&self, newer: &Self) -> Vec<SectionChange> {
+        let mut unmatched: Vec<&PromptSection> = newer.sections.iter().collect();
+        let mut changes = Vec::new();
+
+        for section in &self.sections {
+            match unmatched.iter().position(|s| *s == section) {
+                Some(index) => {
+                    unmatched.remove(index);
+                }
+                None => changes.push(SectionChange::Removed(section.clone())),
+            }
+        }
+        changes.extend(
+            unmatched
+                .into_iter()
+                .map(|s| SectionChange::Added(s.clone())),
+        );
+        changes
+    }
+}
+
+/// An undoable edit to a prompt's sections.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PromptDelta {
+    /// Insert `section` at `index`
+    AddSection {
+        index: usize,
+        section: PromptSection,
+    },
+    /// Remove the section at `index`
+    RemoveSection { index: usize },
+    /// Swap the section at `index` for `section`
+    ReplaceSection {
+        index: usize,
+        section: PromptSection,
+    },
+}
+
+impl Prompt {
+    /// Applies `delta` and returns the inverse delta that undoes it.
+    ///
+    /// Fails without modifying the prompt if the index is out of range.
+    pub fn apply(&mut self, delta: PromptDelta) -> Result<PromptDelta> {
+        let len = self.sections.len();
+        match delta {
+            PromptDelta::AddSection { index, section } => {
+                if index > len {
+                    anyhow::bail!("cannot add section at {index}: prompt has {len} sections");
+                }
+                self.sections.insert(index, section);
+                Ok(PromptDelta::RemoveSection { index })
+            }
+            PromptDelta::RemoveSection { index } => {
+                if index >= len {
+                    anyhow::bail!("cannot remove section {index}: prompt has {len} sections");
+                }
+                let section = self.sections.remove(index);
+                Ok(PromptDelta::AddSection { index, section })
+            }
+            PromptDelta::ReplaceSection { index, section } => {
+                let Some(slot) = self.sections.get_mut(index) else {
+                    anyhow::bail!("cannot replace section {index}: prompt has {len} sections");
+                };
+                let previous = std::mem::replace(slot, section);
+                Ok(PromptDelta::ReplaceSection {
+                    index,
+                    section: previous,
+                })
+            }
+        }
+    }
+}
+
+/// Describes what changed between each pair of consecutive prompt versions.
+///
+/// Returns one entry per transition, so `history.len() - 1` entries.
+#[must_use]
+pub fn prompt_changelog(history: &[Prompt]) -> Vec<String> {
+    let labels = LabelSet::default();
+    history
+        .windows(2)
+        .enumerate()
+        .map(|(index, pair)| {
+            let changes: Vec<String> = pair[0]
+                .diff(&pair[1])
+                .iter()
+                .map(|change| match change {
+                    SectionChange::Added(s) => {
+                        format!("added {} \"{}\"", labels.label_for(s), s.content())
+                    }
+                    SectionChange::Removed(s) => {
+                        format!("removed {} \"{}\"", labels.label_for(s), s.content())
+                    }
+                })
+                .collect();
+            let summary = if changes.is_empty() {
+                "no changes".to_string()
+            } else {
+                changes.join("; ")
+            };
+            format!("v{} -> v{}: {summary}", index + 1, index + 2)
+        })
+        .collect()
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════════
+// SECTION: Guardrails
+// ═══════════════════════════════════════════════════════════════════════════════════
+
+/// Reusable safety instructions for banking prompts.
+pub mod guardrails {
+    pub const NO_FABRICATION: &str =
+        "Never fabricate account data, balances, transactions or customer details";
+    pub const PROTECT_PII: &str =
+        "Never reveal full account numbers, card numbers or other personal data";
+    pub const STATE_UNCERTAINTY: &str =
+        "State clearly when information is missing instead of guessing";
+    pub const NO_GUARANTEES: &str =
+        "Do not promise approvals, rates or outcomes on behalf of the bank";
+    pub const NO_LEGAL_ADVICE: &str =
+        "Do not give legal or tax advice; refer the customer to a qualified professional";
+}
+
+/// A named bundle of guardrails to put in front of a prompt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GuardrailSet {
+    /// Data-integrity and privacy rules every banking prompt should carry
+    Core,
+    /// Core rules plus limits for customer-facing communication
+    CustomerFacing,
+}
+
+impl GuardrailSet {
+    /// The guardrail snippets in this set, in the order they are prepended.
+    #[must_use]
+    pub fn snippets(self) -> Vec<&'static str> {
+        let mut snippets = vec![
+            guardrails::NO_FABRICATION,
+            guardrails::PROTECT_PII,
+            guardrails::STATE_UNCERTAINTY,
+        ];
+        if self == Self::CustomerFacing {
+            snippets.extend([guardrails::NO_GUARANTEES, guardrails::NO_LEGAL_ADVICE]);
+        }
+        snippets
+    }
+}
+
+impl Prompt {
+    /// Returns a copy of this prompt with the set's guardrails prepended as constraints.
+    #[must_use]
+    pub fn with_guardrails(&self, set: GuardrailSet) -> Self {
+        let mut guarded = self.clone();
+        guarded.sections.splice(
+            0..0,
+            set.snippets()
+                .into_iter()
+                .map(|snippet| PromptSection::Constraint(snippet.to_string())),
+        );
+        guarded
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════════
+// SECTION: Regulatory Citations
+// ═══════════════════════════════════════════════════════════════════════════════════
+
+/// Common banking regulations a generated analysis can be required to cite.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Regulation {
+    Ecoa,
+    Fcra,
+    Tila,
+    Bsa,
+    RegE,
+    Glba,
+}
+
+impl Regulation {
+    /// Short name used when citing the regulation.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Ecoa => "ECOA",
+            Self::Fcra => "FCRA",
+            Self::Tila => "TILA",
+            Self::Bsa => "BSA",
+            Self::RegE => "Regulation E",
+            Self::Glba => "GLBA",
+        }
+    }
+
+    /// What the regulation covers.
+    #[must_use]
+    pub const fn description(self) -> &'static str {
+        match self {
+            Self::Ecoa => "Equal Credit Opportunity Act: prohibits discrimination in credit decisions",
+            Self::Fcra => "Fair Credit Reporting Act: governs use of consumer credit reports",
+            Self::Tila => "Truth in Lending Act: requires clear disclosure of credit terms",
+            Self::Bsa => "Bank Secrecy Act: anti-money-laundering reporting and recordkeeping",
+            Self::RegE => "Electronic Fund Transfer Act: error resolution and liability for electronic transfers",
+            Self::Glba => "Gramm-Leach-Bliley Act: protection of customer financial information",
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════════
+// SECTION: Builder Pattern
+// ═══════════════════════════════════════════════════════════════════════════════════
+
+/// What `PromptBuilder` does with a section longer than its configured limit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SectionLimitMode {
+    /// Cut the content down to the limit
+    #[default]
+    Truncate,
+    /// Make `try_build` fail; `build` falls back to truncating
+    Error,
+}
+
+/// Builder for creating prompts using a fluent API.
+#[derive(Default)]
+pub struct PromptBuilder {
+    prompt: Prompt,
+    max_section_len: Option<usize>,
+    limit_mode: SectionLimitMode,
+    limit_error: Option<String>,
+}
+
+impl PromptBuilder {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            prompt: Prompt::new(),
+            max_section_len: None,
+            limit_mode: SectionLimitMode::Truncate,
+            limit_error: None,
+        }
+    }
+
+    /// Limits every section added from now on to `max_chars` characters
+    #[must_use]
+    pub const fn with_max_section_len(mut self, max_chars: usize) -> Self {
+        self.max_section_len = Some(max_chars);
+        self
+    }
+
+    /// Chooses whether over-long sections are truncated or rejected
+    #[must_use]
+    pub const fn section_limit_mode(mut self, mode: SectionLimitMode) -> Self {
+        self.limit_mode = mode;
+        self
+    }
+
+    /// Applies the section length limit, returning the section to add.
+    fn checked(&mut self, section: PromptSection) -> PromptSection {
+        let Some(max_chars) = self.max_section_len else {
+            return section;
+        };
+        let len = section.content().chars().count();
+        if len <= max_chars {
+            return section;
+        }
+
+        match self.limit_mode {
+            SectionLimitMode::Truncate => truncate_section(&section, max_chars),
+            SectionLimitMode::Error => {
+                self.limit_error.get_or_insert_with(|| {
+                    format!("section is {len} characters, exceeding the limit of {max_chars}")
+                });
+                section
+            }
+        }
+    }
+
+    fn add(mut self, section: PromptSection) -> Self {
+        let section = self.checked(section);
+        self.prompt.add_section(section);
+        self
+    }
+
+    fn replace(mut self, section: PromptSection) -> Self {
+        let section = self.checked(section);
+        self.prompt.replace_section(section);
+        self
+    }
+
+    /// Sets the goal section, replacing any goal set earlier
+    #[must_use]
+    pub fn goal(self, goal: impl Into<String>) -> Self {
+        self.replace(PromptSection::Goal(goal.into()))
+    }
+
+    /// Sets the role section, replacing any role set earlier
+    #[must_use]
+    pub fn role(self, role: impl Into<String>) -> Self {
+        self.replace(PromptSection::Role(role.into()))
+    }
+
+    /// Adds a context section with background information
+    #[must_use]
+    pub fn context(self, context: impl Into<String>) -> Self {
+        self.add(PromptSection::Context(context.into()))
+    }
+
+    /// Adds a retrieved document as its own context section, headed by its
+    /// title so the model can tell reference material from instructions
+    #[must_use]
+    pub fn reference_doc(self, title: impl Into<String>, body: impl Into<String>) -> Self {
+        self.context(format!(
+            "--- Reference: {} ---\n{}",
+            title.into(),
+            body.into()
+        ))
+    }
+
+    /// Adds a step section
+    #[must_use]
+    pub fn step(self, step: impl Into<String>) -> Self {
+        self.add(PromptSection::Step(step.into()))
+    }
+
+    /// Adds one step per non-blank line, stripping `1.`, `2)`, `-` or `*` prefixes
+    #[must_use]
+    pub fn steps_from_str(self, text: &str) -> Self {
+        text.lines()
+            .map(strip_list_marker)
+            .filter(|line| !line.is_empty())
+            .fold(self, Self::step)
+    }
+
+    /// Adds an output format section
+    #[must_use]
+    pub fn output(self, output: impl Into<String>) -> Self {
+        self.add(PromptSection::Output(output.into()))
+    }
+
+    /// Adds a constraint section
+    #[must_use]
+    pub fn constraint(self, constraint: impl Into<String>) -> Self {
+        self.add(PromptSection::Constraint(constraint.into()))
+    }
+
+    /// Adds a "Do not" directive, rendered together with the others under `Avoid:`
+    #[must_use]
+    pub fn avoid(self, directive: impl Into<String>) -> Self {
+        self.add(PromptSection::Avoid(directive.into()))
+    }
+
+    /// Adds a sample response for the model to match in tone and style; may be repeated
+    #[must_use]
+    pub fn tone_example(self, text: impl Into<String>) -> Self {
+        self.add(PromptSection::ToneExample(format!(
+            "Match the tone and style of this example: {}",
+            text.into()
+        )))
+    }
+
+    /// When enabled, asks the model to reason step by step and to keep that
+    /// reasoning separate from its final answer
+    #[must_use]
+    pub fn with_chain_of_thought(self, enabled: bool) -> Self {
+        if !enabled {
+            return self;
+        }
+        self.step("Think step by step and show your reasoning before the final answer")
+            .output(
+                "Put your reasoning under 'Reasoning:' and the final answer under 'Conclusion:'",
+            )
+    }
+
+    /// Adds a constraint requiring the response to cite the given regulations
+    #[must_use]
+    pub fn with_citations(self, regulations: &[Regulation]) -> Self {
+        if regulations.is_empty() {
+            return self;
+        }
+        let cited = regulations
+            .iter()
+            .map(|r| format!("{} ({})", r.name(), r.description()))
+            .collect::<Vec<_>>()
+            .join("; ");
+        self.constraint(format!(
+            "Reference the applicable regulations by name in your analysis: {cited}"
+        ))
+    }
+
+    /// Adds an output instruction to answer with exactly one of the given decision codes
+    #[must_use]
+    pub fn decision_codes(self, codes: &[&str]) -> Self {
+        self.output(format!(
+            "Respond with exactly one of these decision codes: {}, followed by a brief rationale",
+            codes.join(", ")
+        ))
+    }
+
+    /// Adds an output instruction to name the escalation chain when risk is high
+    #[must_use]
+    pub fn escalation_path(self, path: &[&str]) -> Self {
+        self.output(format!(
+            "If risk is high, state the escalation path in order: {}",
+            path.join(" -> ")
+        ))
+    }
+
+    /// Marks `{name}` as a required slot, to be filled with `Prompt::fill_slot`,
+    /// `Prompt::render_with` or `Prompt::render_with_strict`
+    ///
+    /// If no section references `{name}` yet, the placeholder is appended to the
+    /// last section, or added as a Context section when the prompt is empty.
+    #[must_use]
+    pub fn slot(mut self, name: impl Into<String>) -> Self {
+        let name = name.into();
+        let placeholder = format!("{{{name}}}");
+        let referenced = self
+            .prompt
+            .sections
+            .iter()
+            .any(|s| s.content().contains(&placeholder));
+        if !referenced {
+            match self.prompt.sections.last_mut() {
+                Some(last) => {
+                    *last = last.with_content(format!("{} {placeholder}", last.content()));
+                }
+                None => self.prompt.add_section(PromptSection::Context(placeholder)),
+            }
+        }
+        if !self.prompt.required_slots.contains(&name) {
+            self.prompt.required_slots.push(name);
+        }
+        self
+    }
+
+    /// Finishes building and returns the prompt
+    ///
+    /// Sections over the limit are always truncated here, even in
+    /// `SectionLimitMode::Error`; use `try_build` to reject them instead.
+    #[must_use]
+    pub fn build(self) -> Prompt {
+        match (self.limit_error, self.max_section_len) {
+            (Some(_), Some(max_chars)) => self.prompt.map_sections(|section| {
+                if section.content().chars().count() > max_chars {
+                    truncate_section(section, max_chars)
+                } else {
+                    section.clone()
+                }
+            }),
+            _ => self.prompt,
+        }
+    }
+
+    /// Finishes building, failing if a section exceeded the length limit in error mode
+    pub fn try_build(self) -> Result<Prompt> {
+        match self.limit_error {
+            Some(err) => anyhow::bail!(err),
+            None => Ok(self.prompt),
+        }
+    }
+}
+
+/// Cuts a section's content down to `max_chars` characters.
+fn truncate_section(section: &PromptSection, max_chars: usize) -> PromptSection {
+    section.with_content(
+        section
+            .content()
+            .chars()
+            .take(max_chars)
+            .collect::<String>(),
+    )
+}
+
+/// Removes a leading list marker such as `1. `, `2) `, `- ` or `* ` from a line.
+///
+/// A marker only counts when followed by whitespace, so text like `1.5%` is kept intact.
+fn strip_list_marker(line: &str) -> &str {
+    let line = line.trim();
+    let digits = line.len() - line.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    let after_marker = if digits > 0 {
+        line[digits..]
+            .strip_prefix('.')
+            .or_else(|| line[digits..].strip_prefix(')'))
+    } else {
+        line.strip_prefix('-').or_else(|| line.strip_prefix('*'))
+    };
+    match after_marker {
+        Some(rest) if rest.is_empty() || rest.starts_with(char::is_whitespace) => rest.trim(),
+        _ => line,
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════════
+// SECTION: LLM Client Interface
+// ═══════════════════════════════════════════════════════════════════════════════════
+
+/// Capabilities a client reports so callers can size prompts for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelInfo {
+    pub name: String,
+    pub max_context_tokens: usize,
+    pub supports_streaming: bool,
+}
+
+impl ModelInfo {
+    /// Conservative description for clients that don't report their model.
+    #[must_use]
+    pub fn unknown() -> Self {
+        Self {
+            name: "unknown".to_string(),
+            max_context_tokens: 4096,
+            supports_streaming: false,
+        }
+    }
+}
+
+/// Ordered outcomes of `SimpleLLMClient::generate_batch`, one per prompt.
+#[derive(Debug)]
+pub struct BatchResult {
+    results: Vec<Result<String>>,
+}
+
+impl From<Vec<Result<String>>> for BatchResult {
+    fn from(results: Vec<Result<String>>) -> Self {
+        Self { results }
+    }
+}
+
+impl BatchResult {
+    /// Responses of the prompts that succeeded, in input order.
+    #[must_use]
+    pub fn successes(&self) -> Vec<&str> {
+        self.results
+            .iter()
+            .filter_map(|r| r.as_ref().ok())
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Input index and error of every prompt that failed.
+    #[must_use]
+    pub fn failures(&self) -> Vec<(usize, &anyhow::Error)> {
+        self.results
+            .iter()
+            .enumerate()
+            .filter_map(|(index, r)| r.as_ref().err().map(|err| (index, err)))
+            .collect()
+    }
+
+    /// Fraction of prompts that succeeded, from 0.0 to 1.0 (0.0 for an empty batch).
+    #[must_use]
+    pub fn success_rate(&self) -> f64 {
+        if self.results.is_empty() {
+            return 0.0;
+        }
+        self.successes().len() as f64 / self.results.len() as f64
+    }
+
+    /// The raw per-prompt results, in input order.
+    #[must_use]
+    pub fn into_results(self) -> Vec<Result<String>> {
+        self.results
+    }
+}
+
+/// Sampling options for a single generation.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct GenOptions {
+    pub temperature: Option<f32>,
+    /// Fixed seed for reproducible output on audited decisions
+    pub seed: Option<u64>,
+    pub max_tokens: Option<usize>,
+}
+
+/// Simple interface for communicating with LLMs.
+#[async_trait]
+pub trait SimpleLLMClient: Send + Sync {
+    /// Sends a prompt to the LLM and gets a response.
+    async fn generate(&self, prompt: &str) -> Result<String>;
+
+    /// Describes the model behind this client, e.g. to `Prompt::truncate_to` its context size.
+    fn model_info(&self) -> ModelInfo {
+        ModelInfo::unknown()
+    }
+
+    /// Sends each prompt in turn, keeping every outcome in input order.
+    async fn generate_batch(&self, prompts: &[&str]) -> BatchResult {
+        let mut results = Vec::with_capacity(prompts.len());
+        for prompt in prompts {
+            results.push(self.generate(prompt).await);
+        }
+        BatchResult::from(results)
+    }
+
+    /// Checks the backend is reachable by sending a trivial prompt and
+    /// expecting a non-empty answer.
+    async fn health_check(&self) -> Result<()> {
+        let response = self.generate("ping").await?;
+        if response.trim().is_empty() {
+            anyhow::bail!("health check returned an empty response");
+        }
+        Ok(())
+    }
+
+    /// Sends a prompt with sampling options. Clients that don't support them
+    /// fall back to plain `generate`.
+    async fn generate_with_options(&self, prompt: &str, _opts: GenOptions) -> Result<String> {
+        self.generate(prompt).await
+    }
+
+    /// Like `generate`, but gives up with a "generation cancelled" error as soon
+    /// as `cancel` is triggered.
+    async fn generate_cancellable(
+        &self,
+        prompt: &str,
+        cancel: CancellationToken,
+    ) -> Result<String> {
+        tokio::select! {
+            biased;
+            () = cancel.cancelled() => anyhow::bail!("generation cancelled"),
+            response = self.generate(prompt) => response,
+        }
+    }
+}
+
+/// Boxed clients are clients too, so every wrapper accepts `Box<dyn SimpleLLMClient>`.
+#[async_trait]
+impl<T: SimpleLLMClient + ?Sized> SimpleLLMClient for Box<T> {
+    async fn generate(&self, prompt: &str) -> Result<String> {
+        (**self).generate(prompt).await
+    }
+
+    async fn generate_with_options(&self, prompt: &str, opts: GenOptions) -> Result<String> {
+        (**self).generate_with_options(prompt, opts).await
+    }
+
+    async fn generate_batch(&self, prompts: &[&str]) -> BatchResult {
+        (**self).generate_batch(prompts).await
+    }
+
+    async fn generate_cancellable(
+        &self,
+        prompt: &str,
+        cancel: CancellationToken,
+    ) -> Result<String> {
+        (**self).generate_cancellable(prompt, cancel).await
+    }
+
+    async fn health_check(&self) -> Result<()> {
+        (**self).health_check().await
+    }
+
+    fn model_info(&self) -> ModelInfo {
+        (**self).model_info()
+    }
+}
+
+/// Erases a client's concrete type into a `Box<dyn SimpleLLMClient>`.
+#[must_use]
+pub fn boxed(client: impl SimpleLLMClient + 'static) -> Box<dyn SimpleLLMClient> {
+    Box::new(client)
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════════
+// SECTION: Mock LLM Client
+// ═══════════════════════════════════════════════════════════════════════════════════
+
+/// Mock LLM client for demonstration and testing.
+pub struct MockLLMClient;
+
+impl MockLLMClient {
+    /// Context window the mock declares through `model_info`.
+    pub const MAX_CONTEXT_TOKENS: usize = 8192;
+}
+
+#[async_trait]
+impl SimpleLLMClient for MockLLMClient {
+    /// Returns a mock response based on prompt content.
+    async fn generate(&self, prompt: &str) -> Result<String> {
+        // Simulate network delay
+        tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+
+        // Simple responses based on banking prompt content
+        if prompt.contains("credit risk") || prompt.contains("Credit Risk") {
+            Ok("CREDIT ANALYSIS COMPLETE\n\nApplicant Profile: FICO 720, DTI 28%, Stable Employment\nRisk Assessment: LOW RISK (2.1% default probability)\nRecommendation: APPROVED at Prime + 1.25%\nRequired: Income verification, property appraisal".to_string())
+        } else if prompt.contains("fraud") || prompt.contains("Fraud") {
+            Ok("FRAUD ALERT ISSUED\n\nTransaction Pattern: Multiple ATM withdrawals detected\nRisk Level: HIGH (Score 85/100)\nGeographic Anomaly: 500+ miles from normal location\nAction Required: FREEZE card, contact customer immediately".to_string())
+        } else {
+            Ok("Analysis complete. Banking task processed according to regulatory guidelines and best practices.".to_string())
+        }
+    }
+
+    /// Appends a confidence note chosen deterministically from the seed.
+    async fn generate_with_options(&self, prompt: &str, opts: GenOptions) -> Result<String> {
+        const CONFIDENCE: [&str; 3] = ["high", "moderate", "low"];
+
+        let response = self.generate(prompt).await?;
+        Ok(match opts.seed {
+            Some(seed) => {
+                let level = CONFIDENCE[(seed % CONFIDENCE.len() as u64) as usize];
+                format!("{response}\nModel confidence: {level}")
+            }
+            None => response,
+        })
+    }
+
+    fn model_info(&self) -> ModelInfo {
+        ModelInfo {
+            name: "mock-banking-llm".to_string(),
+            max_context_tokens: Self::MAX_CONTEXT_TOKENS,
+            supports_streaming: false,
+        }
+    }
+}
+
+/// Mock client that answers with the prompt it received, for inspecting what
+/// decorators actually send.
+pub struct EchoClient;
+
+#[async_trait]
+impl SimpleLLMClient for EchoClient {
+    async fn generate(&self, prompt: &str) -> Result<String> {
+        Ok(prompt.to_string())
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════════
+// SECTION: Client Decorators
+// ═══════════════════════════════════════════════════════════════════════════════════
+
+/// Wraps a client and counts how many times `generate` reaches it.
+///
+/// Useful in tests for asserting how often a decorator actually calls the
+/// underlying client.
+pub struct CountingClient<C> {
+    inner: C,
+    calls: AtomicUsize,
+}
+
+impl<C> CountingClient<C> {
+    #[must_use]
+    pub const fn new(inner: C) -> Self {
+        Self {
+            inner,
+            calls: AtomicUsize::new(0),
+        }
+    }
+
+    /// Number of `generate` calls forwarded to the inner client so far.
+    #[must_use]
+    pub fn call_count(&self) -> usize {
+        self.calls.load(Ordering::SeqCst)
+    }
+}
+
+#[async_trait]
+impl<C: SimpleLLMClient> SimpleLLMClient for CountingClient<C> {
+    async fn generate(&self, prompt: &str) -> Result<String> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        self.inner.generate(prompt).await
+    }
+
+    async fn generate_with_options(&self, prompt: &str, opts: GenOptions) -> Result<String> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        self.inner.generate_with_options(prompt, opts).await
+    }
+
+    fn model_info(&self) -> ModelInfo {
+        self.inner.model_info()
+    }
+}
+
+/// Caps responses at `max_chars`, cutting at a word boundary and adding `…`.
+pub struct TruncatingClient<C> {
+    inner: C,
+    max_chars: usize,
+}
+
+impl<C> TruncatingClient<C> {
+    #[must_use]
+    pub const fn new(inner: C, max_chars: usize) -> Self {
+        Self { inner, max_chars }
+    }
+
+    fn truncate(&self, response: String) -> String {
+        if response.chars().count() <= self.max_chars {
+            return response;
+        }
+
+        // Leave room for the ellipsis, then back off to the last whole word
+        let keep = self.max_chars.saturating_sub(1);
+        let cut: String = response.chars().take(keep).collect();
+        let ends_on_word = response.chars().nth(keep).is_some_and(char::is_whitespace);
+        let at_word = match cut.rfind(char::is_whitespace) {
+            Some(index) if !ends_on_word => &cut[..index],
+            _ => cut.as_str(),
+        };
+        let mut truncated = at_word.trim_end().to_string();
+        if self.max_chars > 0 {
+            truncated.push('…');
+        }
+        truncated
+    }
+}
+
+#[async_trait]
+impl<C: SimpleLLMClient> SimpleLLMClient for TruncatingClient<C> {
+    async fn generate(&self, prompt: &str) -> Result<String> {
+        Ok(self.truncate(self.inner.generate(prompt).await?))
+    }
+
+    async fn generate_with_options(&self, prompt: &str, opts: GenOptions) -> Result<String> {
+        Ok(self.truncate(self.inner.generate_with_options(prompt, opts).await?))
+    }
+
+    fn model_info(&self) -> ModelInfo {
+        self.inner.model_info()
+    }
+}
+
+/// Separates the `PreambleClient` preamble from the caller's prompt.
+pub const PREAMBLE_DELIMITER: &str = "\n\n--- END OF SYSTEM PREAMBLE ---\n\n";
+
+/// Prepends a fixed preamble (branding, compliance rules) to every prompt.
+pub struct PreambleClient<C> {
+    inner: C,
+    preamble: String,
+}
+
+impl<C> PreambleClient<C> {
+    #[must_use]
+    pub fn new(inner: C, preamble: impl Into<String>) -> Self {
+        Self {
+            inner,
+            preamble: preamble.into(),
+        }
+    }
+
+    fn with_preamble(&self, prompt: &str) -> String {
+        format!("{}{PREAMBLE_DELIMITER}{prompt}", self.preamble)
+    }
+}
+
+#[async_trait]
+impl<C: SimpleLLMClient> SimpleLLMClient for PreambleClient<C> {
+    async fn generate(&self, prompt: &str) -> Result<String> {
+        self.inner.generate(&self.with_preamble(prompt)).await
+    }
+
+    async fn generate_with_options(&self, prompt: &str, opts: GenOptions) -> Result<String> {
+        self.inner
+            .generate_with_options(&self.with_preamble(prompt), opts)
+            .await
+    }
+
+    fn model_info(&self) -> ModelInfo {
+        self.inner.model_info()
+    }
+}
+
+/// Reports what would be sent to the inner client instead of calling it.
+///
+/// Useful for debugging prompts and estimating cost without spending tokens.
+pub struct DryRunClient<C> {
+    inner: C,
+}
+
+impl<C> DryRunClient<C> {
+    #[must_use]
+    pub const fn new(inner: C) -> Self {
+        Self { inner }
+    }
+}
+
+impl<C: SimpleLLMClient> DryRunClient<C> {
+    /// Describes the request, including its sampling options when given.
+    fn report(&self, prompt: &str, opts: Option<GenOptions>) -> String {
+        let info = self.inner.model_info();
+        let options = opts
+            .map(|opts| format!("Options: {opts:?}\n"))
+            .unwrap_or_default();
+        format!(
+            "[DRY RUN] Request not sent\nModel: {}\nMax context tokens: {}\nEstimated prompt tokens: {}\n{options}--- Prompt ---\n{prompt}",
+            info.name,
+            info.max_context_tokens,
+            HeuristicTokenizer.count_tokens(prompt)
+        )
+    }
+}
+
+#[async_trait]
+impl<C: SimpleLLMClient> SimpleLLMClient for DryRunClient<C> {
+    async fn generate(&self, prompt: &str) -> Result<String> {
+        Ok(self.report(prompt, None))
+    }
+
+    async fn generate_with_options(&self, prompt: &str, opts: GenOptions) -> Result<String> {
+        Ok(self.report(prompt, Some(opts)))
+    }
+
+    fn model_info(&self) -> ModelInfo {
+        self.inner.model_info()
+    }
+}
+
+/// One line of the audit trail written by `AuditClient`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// Seconds since the Unix epoch
+    pub timestamp: u64,
+    pub prompt: String,
+    pub response: String,
+    /// FNV-1a hash of prompt and response, hex-encoded
+    pub checksum: String,
+    /// Error message if the call failed, in which case `response` is empty
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Appends every interaction, successful or failed, to a JSON-lines audit file.
+///
+/// The file is created if missing; writes are serialized so concurrent calls
+/// never interleave lines.
+pub struct AuditClient<C> {
+    inner: C,
+    path: PathBuf,
+    write_lock: tokio::sync::Mutex<()>,
+}
+
+impl<C> AuditClient<C> {
+    #[must_use]
+    pub fn new(inner: C, path: impl Into<PathBuf>) -> Self {
+        Self {
+            inner,
+            path: path.into(),
+            write_lock: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// Appends one entry for `outcome` to the audit file.
+    async fn record(&self, prompt: &str, outcome: &Result<String>) -> Result<()> {
+        use tokio::io::AsyncWriteExt;
+
+        let (response, error) = match outcome {
+            Ok(response) => (response.clone(), None),
+            Err(err) => (String::new(), Some(format!("{err:#}"))),
+        };
+        let checksum = format!(
+            "{:016x}",
+            fnv1a_64(
+                [prompt.as_bytes(), &[0], response.as_bytes()]
+                    .concat()
+                    .as_slice()
+            )
+        );
+        let entry = AuditEntry {
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)?
+                .as_secs(),
+            prompt: prompt.to_string(),
+            response,
+            checksum,
+            error,
+        };
+        let mut line = serde_json::to_string(&entry)?;
+        line.push('\n');
+
+        let _guard = self.write_lock.lock().await;
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await?;
+        file.write_all(line.as_bytes()).await?;
+        file.flush().await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<C: SimpleLLMClient> SimpleLLMClient for AuditClient<C> {
+    async fn generate(&self, prompt: &str) -> Result<String> {
+        let outcome = self.inner.generate(prompt).await;
+        self.record(prompt, &outcome).await?;
+        outcome
+    }
+
+    async fn generate_with_options(&self, prompt: &str, opts: GenOptions) -> Result<String> {
+        let outcome = self.inner.generate_with_options(prompt, opts).await;
+        self.record(prompt, &outcome).await?;
+        outcome
+    }
+
+    fn model_info(&self) -> ModelInfo {
+        self.inner.model_info()
+    }
+}
+
+/// 64-bit FNV-1a hash; unlike `DefaultHasher` it is stable across Rust releases.
+fn fnv1a_64(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════════
+// SECTION: Ensemble Client
+// ═══════════════════════════════════════════════════════════════════════════════════
+
+/// How an `EnsembleClient` combines the responses of its members.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AggregationStrategy {
+    /// Every successful response, each prefixed with its member's name
+    Concatenate,
+    /// The response with the highest total member weight
+    Majority,
+    /// The first non-empty response, in the order members were added
+    FirstNonEmpty,
+}
+
+struct EnsembleMember {
+    name: String,
+    client: Arc<dyn SimpleLLMClient>,
+    weight: f64,
+}
+
+/// Queries several clients concurrently and aggregates their answers.
+pub struct EnsembleClient {
+    members: Vec<EnsembleMember>,
+    strategy: AggregationStrategy,
+}
+
+impl EnsembleClient {
+    #[must_use]
+    pub const fn new(strategy: AggregationStrategy) -> Self {
+        Self {
+            members: Vec::new(),
+            strategy,
+        }
+    }
+
+    /// Adds a named member whose vote counts `weight` under `Majority`
+    #[must_use]
+    pub fn with_client(
+        mut self,
+        name: impl Into<String>,
+        client: impl SimpleLLMClient + 'static,
+        weight: f64,
+    ) -> Self {
+        self.members.push(EnsembleMember {
+            name: name.into(),
+            client: Arc::new(client),
+            weight,
+        });
+        self
+    }
+
+    /// Queries every member, passing `opts` through when given, and aggregates
+    /// the responses.
+    async fn run(&self, prompt: &str, opts: Option<GenOptions>) -> Result<String> {
+        if self.members.is_empty() {
+            anyhow::bail!("ensemble has no clients configured");
+        }
+
+        // A JoinSet aborts any still-running member calls if this future is dropped
+        let mut tasks = tokio::task::JoinSet::new();
+        let mut member_index = std::collections::HashMap::new();
+        for (index, member) in self.members.iter().enumerate() {
+            let client = Arc::clone(&member.client);
+            let prompt = prompt.to_string();
+            let handle = tasks.spawn(async move {
+                match opts {
+                    Some(opts) => client.generate_with_options(&prompt, opts).await,
+                    None => client.generate(&prompt).await,
+                }
+            });
+            member_index.insert(handle.id(), index);
+        }
+
+        let mut outcomes: Vec<Option<Result<String>>> = self.members.iter().map(|_| None).collect();
+        while let Some(joined) = tasks.join_next_with_id().await {
+            // A panicking member only fails its own slot, not the whole ensemble
+            let (id, outcome) = match joined {
+                Ok((id, outcome)) => (id, outcome),
+                Err(err) => (
+                    err.id(),
+                    Err(anyhow::anyhow!("ensemble member failed: {err}")),
+                ),
+            };
+            outcomes[member_index[&id]] = Some(outcome);
+        }
+
+        let mut responses = Vec::new();
+        let mut first_error = None;
+        for (member, outcome) in self.members.iter().zip(outcomes.into_iter().flatten()) {
+            match outcome {
+                Ok(response) => responses.push((member, response)),
+                Err(err) => {
+                    first_error.get_or_insert(err);
+                }
+            }
+        }
+
+        let aggregated = match self.strategy {
+            AggregationStrategy::Concatenate => (!responses.is_empty()).then(|| {
+                responses
+                    .iter()
+                    .map(|(member, response)| format!("[{}]\n{response}", member.name))
+                    .collect::<Vec<_>>()
+                    .join("\n\n")
+            }),
+            AggregationStrategy::Majority => {
+                let mut tallies: Vec<(&str, f64)> = Vec::new();
+                for (member, response) in &responses {
+                    let answer = response.trim();
+                    match tallies.iter_mut().find(|(a, _)| *a == answer) {
+                        Some((_, total)) => *total += member.weight,
+                        None => tallies.push((answer, member.weight)),
+                    }
+                }
+                // Ties go to the answer seen first, so results follow member order
+                tallies
+                    .into_iter()
+                    .rev()
+                    .max_by(|a, b| a.1.total_cmp(&b.1))
+                    .map(|(answer, _)| answer.to_string())
+            }
+            AggregationStrategy::FirstNonEmpty => responses
+                .into_iter()
+                .map(|(_, response)| response)
+                .find(|response| !response.trim().is_empty()),
+        };
+
+        match (aggregated, first_error) {
+            (Some(response), _) => Ok(response),
+            (None, Some(err)) => Err(err),
+            (None, None) => anyhow::bail!("ensemble produced no usable response"),
+        }
+    }
+}
+
+#[async_trait]
+impl SimpleLLMClient for EnsembleClient {
+    async fn generate(&self, prompt: &str) -> Result<String> {
+        self.run(prompt, None).await
+    }
+
+    async fn generate_with_options(&self, prompt: &str, opts: GenOptions) -> Result<String> {
+        self.run(prompt, Some(opts)).await
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════════
+// SECTION: Embedding Interface
+// ═══════════════════════════════════════════════════════════════════════════════════
+
+/// Interface for models that turn text into vectors, e.g. for fraud similarity search.
+#[async_trait]
+pub trait EmbeddingClient: Send + Sync {
+    /// Returns the embedding vector for `text`.
+    async fn embed(&self, text: &str) -> Result<Vec<f32>>;
+}
+
+/// Deterministic embedding mock: the same text always yields the same unit vector,
+/// across runs and Rust releases.
+pub struct MockEmbeddingClient;
+
+impl MockEmbeddingClient {
+    /// Length of every vector the mock returns.
+    pub const DIMENSIONS: usize = 64;
+}
+
+#[async_trait]
+impl EmbeddingClient for MockEmbeddingClient {
+    async fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        let raw: Vec<f32> = (0..Self::DIMENSIONS)
+            .map(|dimension| {
+                let bytes = [text.as_bytes(), &[0], &dimension.to_le_bytes()].concat();
+                // Map the hash onto [-1.0, 1.0]
+                (fnv1a_64(&bytes) % 2001) as f32 / 1000.0 - 1.0
+            })
+            .collect();
+
+        let norm = raw.iter().map(|x| x * x).sum::<f32>().sqrt();
+        Ok(raw
+            .into_iter()
+            .map(|x| if norm > 0.0 { x / norm } else { x })
+            .collect())
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════════
+// SECTION: Compliance Helpers
+// ═══════════════════════════════════════════════════════════════════════════════════
+
+/// Placeholder left in attestation blocks for the caller to stamp with a real time.
+pub const TIMESTAMP_PLACEHOLDER: &str = "{timestamp}";
+
+/// Appends a standard compliance attestation block after an LLM response.
+#[must_use]
+pub fn wrap_with_attestation(response: &str, attestor: &str) -> String {
+    format!(
+        "{response}\n\n--- Compliance Attestation ---\nAttested by: {attestor}\nTimestamp: {TIMESTAMP_PLACEHOLDER}\nThis output was reviewed before release."
+    )
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════════
+// SECTION: Regression Fixtures
+// ═══════════════════════════════════════════════════════════════════════════════════
+
+/// A captured prompt/response pair for regression tests.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TestCaseFixture {
+    pub prompt: Prompt,
+    pub rendered: String,
+    pub response: String,
+}
+
+/// Captures a prompt and the response it produced as a JSON fixture.
+#[must_use]
+pub fn capture_test_case(prompt: &Prompt, response: &str) -> String {
+    let fixture = TestCaseFixture {
+        prompt: prompt.clone(),
+        rendered: prompt.to_string(),
+        response: response.to_string(),
+    };
+    serde_json::to_string_pretty(&fixture).expect("fixtures only contain strings")
+}
+
+/// Loads a fixture produced by `capture_test_case`.
+pub fn load_test_case(json: &str) -> Result<TestCaseFixture> {
+    Ok(serde_json::from_str(json)?)
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════════
+// SECTION: Banking Templates
+// ═══════════════════════════════════════════════════════════════════════════════════
+
+/// Pre-built templates for common banking use cases.
+#[derive(Debug, Clone)]
+pub enum BankingTemplate {
+    /// Credit risk assessment and loan evaluation
+    CreditRisk { loan_type: String, focus: String },
+    /// Fraud detection and prevention
+    FraudDetection { channel: String, scope: String },
+    /// Wealth-management portfolio review
+    PortfolioReview {
+        client_profile: String,
+        objective: String,
+    },
+    /// Regulator-facing CFPB complaint narrative
+    CfpbNarrative {
+        complaint_type: String,
+        outcome: String,
+    },
+    /// Trade finance and letter-of-credit review
+    TradeFinance {
+        instrument: String,
+        discrepancy_focus: String,
+    },
+    /// Real-time P2P / Zelle scam detection
+    P2pScam { scam_type: String, signals: String },
+    /// Operational-risk RCSA assessment
+    Rcsa {
+        process: String,
+        risk_category: String,
+    },
+    /// Teller-line cash structuring detection
+    CashStructuring { threshold: String, window: String },
+    /// Retail customer complaint resolution
+    ComplaintResolution { product: String, severity: String },
+    /// Financial-statement analysis for commercial lending
+    StatementAnalysis { entity_type: String, period: String },
+}
+
+impl BankingTemplate {
+    /// Creates a pre-configured prompt builder.
+    #[must_use]
+    pub fn to_builder(&self) -> PromptBuilder {
+        match self {
+            Self::CreditRisk { loan_type, focus } => PromptBuilder::new()
+                .goal(format!(
+                    "Assess credit risk for {loan_type} focusing on {focus}"
+                ))
+                .role("Senior Credit Risk Analyst")
+                .step("Analyze credit history and payment patterns")
+                .step("Evaluate income stability and debt ratios")
+                .step("Calculate default probability and risk rating")
+                .step("Determine loan terms and interest rates")
+                .output("Risk assessment with approval recommendation"),
+            Self::FraudDetection { channel, scope } => PromptBuilder::new()
+                .goal(format!("Detect fraud in {channel} using {scope}"))
+                .role("Fraud Detection Specialist")
+                .step("Analyze transaction patterns and anomalies")
+                .step("Apply fraud scoring models")
+                .step("Check against known risk indicators")
+                .step("Generate alerts and recommended actions")
+                .output("Fraud risk assessment with action plan"),
+            Self::PortfolioReview {
+                client_profile,
+                objective,
+            } => PromptBuilder::new()
+                .goal(format!(
+                    "Review the portfolio of a {client_profile} client against the objective of {objective}"
+                ))
+                .role("Wealth Advisor")
+                .step("Analyze current asset allocation and concentration")
+                .step("Assess alignment with the client's risk tolerance")
+                .step("Suggest rebalancing actions toward target allocation")
+                .step("Identify tax considerations for proposed changes")
+                .output("Portfolio review with rebalancing recommendations"),
+            Self::CfpbNarrative {
+                complaint_type,
+                outcome,
+            } => PromptBuilder::new()
+                .goal(format!(
+                    "Draft a CFPB response narrative for a {complaint_type} complaint resolved with {outcome}"
+                ))
+                .role("Regulatory Response Analyst")
+                .step("Summarize the relevant facts of the complaint")
+                .step("Recap the investigation performed and its findings")
+                .step("State the resolution provided to the consumer")
+                .step("Frame the response against applicable consumer-protection requirements")
+                .output("CFPB complaint narrative ready for regulator submission"),
+            Self::TradeFinance {
+                instrument,
+                discrepancy_focus,
+            } => PromptBuilder::new()
+                .goal(format!(
+                    "Review {instrument} documents focusing on {discrepancy_focus}"
+                ))
+                .role("Trade Finance Officer")
+                .step("Examine presented documents against the credit terms")
+                .step("Identify discrepancies between documents and credit conditions")
+                .step("Check compliance with UCP 600 rules")
+                .step("Recommend disposition: honor, refuse, or seek waiver")
+                .output("Letter-of-credit review with discrepancies and disposition"),
+            Self::P2pScam {
+                scam_type,
+                signals,
+            } => PromptBuilder::new()
+                .goal(format!(
+                    "Detect {scam_type} scams in P2P payments using {signals}"
+                ))
+                .role("Fraud Analyst")
+                .step("Analyze behavioral signals around the payment session")
+                .step("Match activity against known scam patterns")
+                .step("Detect signs the customer is being coached or coerced")
+                .step("Recommend an intervention before funds are released")
+                .output("Scam-risk decision with recommended intervention"),
+            Self::Rcsa {
+                process,
+                risk_category,
+            } => PromptBuilder::new()
+                .goal(format!(
+                    "Perform a risk and control self-assessment of {process} for {risk_category} risk"
+                ))
+                .role("Operational Risk Analyst")
+                .step("Identify inherent risks in the process")
+                .step("Map existing controls to each identified risk")
+                .step("Rate residual risk after controls")
+                .step("Define an action plan for control gaps")
+                .output("RCSA entry with risks, controls, residual rating and actions"),
+            Self::CashStructuring {
+                threshold,
+                window,
+            } => PromptBuilder::new()
+                .goal(format!(
+                    "Detect cash structuring below the {threshold} reporting threshold over {window}"
+                ))
+                .role("BSA Analyst")
+                .step("Aggregate cash transactions per customer across branches and channels")
+                .step("Detect structuring patterns such as split or sequential deposits")
+                .step("Analyze how closely amounts cluster under the reporting threshold")
+                .step("Determine whether a SAR should be considered")
+                .output("Structuring assessment with SAR recommendation"),
+            Self::ComplaintResolution {
+                product,
+                severity,
+            } => PromptBuilder::new()
+                .goal(format!(
+                    "Resolve a {severity} severity customer complaint about {product}"
+                ))
+                .role("Empathetic Customer Support Specialist")
+                .step("Acknowledge the customer's concern and its impact")
+                .step("Investigate the account history and root cause")
+                .step("Propose a fair resolution within policy")
+                .step("Escalate to the appropriate team if policy limits are exceeded")
+                .output("Customer-facing response plus internal case notes"),
+            Self::StatementAnalysis {
+                entity_type,
+                period,
+            } => PromptBuilder::new()
+                .goal(format!(
+                    "Analyze the financial statements of a {entity_type} for {period}"
+                ))
+                .role("Commercial Credit Financial Analyst")
+                .step("Assess liquidity with current and quick ratios")
+                .step("Evaluate leverage with debt-to-equity and debt service coverage ratios")
+                .step("Measure profitability with margin and return ratios")
+                .step("Analyze trends in these ratios and cash flow across periods")
+                .output("Key financial ratios with a creditworthiness summary"),
+        }
+    }
+
+    /// Creates the template's builder with a constraint to cite `regulations`.
+    #[must_use]
+    pub fn to_builder_with_citations(&self, regulations: &[Regulation]) -> PromptBuilder {
+        self.to_builder().with_citations(regulations)
+    }
+
+    /// Creates the template's builder with chain-of-thought reasoning toggled.
+    #[must_use]
+    pub fn to_builder_with_chain_of_thought(&self, enabled: bool) -> PromptBuilder {
+        self.to_builder().with_chain_of_thought(enabled)
+    }
+
+    /// Gets a description of what this template does.
+    #[must_use]
+    pub fn description(&self) -> String {
+        match self {
+            Self::CreditRisk { loan_type, focus } => {
+                format!("Assesses credit risk for {loan_type} focusing on {focus}")
+            }
+            Self::FraudDetection { channel, scope } => {
+                format!("Detects fraud in {channel} using {scope}")
+            }
+            Self::PortfolioReview {
+                client_profile,
+                objective,
+            } => {
+                format!("Reviews a {client_profile} portfolio for {objective}")
+            }
+            Self::CfpbNarrative {
+                complaint_type,
+                outcome,
+            } => format!(
+                "Drafts a CFPB narrative for a {complaint_type} complaint resolved with {outcome}"
+            ),
+            Self::TradeFinance {
+                instrument,
+                discrepancy_focus,
+            } => format!("Reviews {instrument} documents focusing on {discrepancy_focus}"),
+            Self::P2pScam { scam_type, signals } => {
+                format!("Detects {scam_type} scams in P2P payments using {signals}")
+            }
+            Self::Rcsa {
+                process,
+                risk_category,
+            } => format!("Assesses {risk_category} risk and controls in {process}"),
+            Self::CashStructuring { threshold, window } => {
+                format!("Detects cash structuring below {threshold} over {window}")
+            }
+            Self::ComplaintResolution { product, severity } => {
+                format!("Resolves a {severity} severity complaint about {product}")
+            }
+            Self::StatementAnalysis {
+                entity_type,
+                period,
+            } => format!("Analyzes {entity_type} financial statements for {period}"),
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════════
+// SECTION: Demo Function
+// ═══════════════════════════════════════════════════════════════════════════════════
+
+/// Demonstrates the banking prompt library.
+async fn demo_banking_prompts() -> Result<()> {
+    println!("🏦 Simple Banking Prompt Library Demo");
+    println!("=====================================");
+    println!();
+
+    // Manual prompt building
+    println!("📝 Manual Prompt Building:");
+    let manual_prompt = PromptBuilder::new()
+        .goal("Evaluate loan application")
+        .role("Credit Analyst")
+        .step("Review credit score and history")
+        .step("Analyze income and debt ratios")
+        .output("Approval recommendation with terms")
+        .build();
+
+    println!(
+        "✅ Built manually: {} sections",
+        manual_prompt.sections.len()
+    );
+    println!();
+
+    // Template-based building
+    println!("🎯 Template-Based Building:");
+    let template = BankingTemplate::CreditRisk {
+        loan_type: "mortgage".to_string(),
+        focus: "default risk".to_string(),
+    };
+
+    let template_prompt = template.to_builder().build();
+    println!("✅ {}", template.description());
+    println!(
+        "✅ Built from template: {} sections",
+        template_prompt.sections.len()
+    );
+    println!();
+
+    // Test with LLM client
+    println!("🤖 Testing with LLM:");
+    let llm_client = MockLLMClient;
+
+    let response = llm_client.generate(&template_prompt.to_string()).await?;
+    println!("💬 Response:");
+    println!("{response}");
+    println!();
+
+    println!("🎉 Demo completed!");
+    Ok(())
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════════
+// SECTION: Main Function
+// ═══════════════════════════════════════════════════════════════════════════════════
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    demo_banking_prompts().await?;
+
+    println!();
+    println!("📚 Key Learning Points:");
+    println!("   ✅ Builder pattern for fluent APIs");
+    println!("   ✅ Trait abstraction for LLM clients");
+    println!("   ✅ Template system for reusable prompts");
+    println!("   ✅ Async programming with Rust");
     println!("   ✅ Clean, readable code structure");
 
-    Ok(())
-}
+    Ok(())
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════════
+// SECTION: Tests
+// ═══════════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test client that always answers with the same text.
+    struct FixedClient(&'static str);
+
+    #[async_trait]
+    impl SimpleLLMClient for FixedClient {
+        async fn generate(&self, _prompt: &str) -> Result<String> {
+            Ok(self.0.to_string())
+        }
+    }
+
+    /// Test client whose backend is always down.
+    struct FailingClient;
+
+    #[async_trait]
+    impl SimpleLLMClient for FailingClient {
+        async fn generate(&self, _prompt: &str) -> Result<String> {
+            anyhow::bail!("backend unavailable")
+        }
+    }
+
+    #[test]
+    fn test_prompt_builder() {
+        let prompt = PromptBuilder::new()
+            .goal("Test goal")
+            .role("Test role")
+            .step("Test step")
+            .build();
+
+        let text = prompt.to_string();
+        assert!(text.contains("Goal: Test goal"));
+        assert!(text.contains("Role: Test role"));
+        assert!(text.contains("Step: Test step"));
+    }
+
+    #[test]
+    fn test_goal_and_role_replace_previous() {
+        let prompt = PromptBuilder::new()
+            .goal("Draft goal")
+            .role("Teller")
+            .step("Review account")
+            .goal("Final goal")
+            .role("Branch Manager")
+            .build();
+
+        assert_eq!(
+            prompt.to_string(),
+            "Goal: Final goal\nRole: Branch Manager\nStep: Review account"
+        );
+    }
+
+    #[test]
+    fn test_parse_round_trip() {
+        let prompt = BankingTemplate::FraudDetection {
+            channel: "card payments".to_string(),
+            scope: "velocity checks".to_string(),
+        }
+        .to_builder()
+        .step("Summarize findings:\n- merchant\n\n- amount")
+        .build();
+
+        let parsed = Prompt::parse(&prompt.to_string()).unwrap();
+
+        assert_eq!(parsed, prompt);
+    }
+
+    #[test]
+    fn test_parse_unrecognized_lines() {
+        let text = "Goal: Review wire\nextra detail";
+
+        let lenient = Prompt::parse(text).unwrap();
+        assert_eq!(lenient.to_string(), text);
+
+        let strict = Prompt::parse_with(text, &LabelSet::default(), ParseMode::Strict);
+        assert!(strict.unwrap_err().to_string().contains("line 2"));
+        assert!(Prompt::parse("no label here").is_err());
+    }
+
+    #[test]
+    fn test_prompt_changelog() {
+        let v1 = PromptBuilder::new().goal("Review loan").build();
+        let v2 = PromptBuilder::new()
+            .goal("Review loan")
+            .step("Check DTI ratio")
+            .build();
+        let v3 = PromptBuilder::new()
+            .goal("Review mortgage")
+            .step("Check DTI ratio")
+            .build();
+
+        let changelog = prompt_changelog(&[v1, v2, v3]);
+
+        assert_eq!(
+            changelog,
+            vec![
+                "v1 -> v2: added Step \"Check DTI ratio\"".to_string(),
+                "v2 -> v3: removed Goal \"Review loan\"; added Goal \"Review mortgage\""
+                    .to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unfilled_required_slot() {
+        let prompt = PromptBuilder::new()
+            .goal("Review account {account_id} for {customer}")
+            .slot("account_id")
+            .slot("customer")
+            .build();
+
+        assert_eq!(prompt.unfilled_slots(), vec!["account_id", "customer"]);
+
+        let partial = prompt.fill_slot("account_id", "ACC-1001");
+        assert_eq!(partial.unfilled_slots(), vec!["customer"]);
+        assert!(prompt.render_with(&[("account_id", "ACC-1001")]).is_err());
+
+        let rendered = prompt
+            .render_with(&[("account_id", "ACC-1001"), ("customer", "Dana Lee")])
+            .unwrap();
+        assert_eq!(rendered, "Goal: Review account ACC-1001 for Dana Lee");
+    }
+
+    #[test]
+    fn test_slot_inserts_placeholder() {
+        let prompt = PromptBuilder::new().goal("Review").slot("customer").build();
+        assert_eq!(prompt.to_string(), "Goal: Review {customer}");
+        assert_eq!(prompt.unfilled_slots(), vec!["customer"]);
+        assert_eq!(
+            prompt.render_with(&[("customer", "Dana")]).unwrap(),
+            "Goal: Review Dana"
+        );
+
+        let empty = PromptBuilder::new().slot("account_id").build();
+        assert_eq!(empty.to_string(), "Context: {account_id}");
+    }
+
+    #[test]
+    fn test_render_with_strict_rejects_misspelled_slot() {
+        let prompt = PromptBuilder::new()
+            .goal("Review {custmer}")
+            .slot("customer")
+            .build();
+
+        let err = prompt
+            .render_with_strict(&[("customer", "Dana")])
+            .unwrap_err();
+        assert!(err.to_string().contains("custmer"));
+
+        let undeclared = PromptBuilder::new().goal("Review {custmer}").build();
+        let err = undeclared
+            .render_with_strict(&[("customer", "Dana")])
+            .unwrap_err();
+        assert!(err.to_string().contains("custmer"));
+
+        // Literal braces are fine outside strict mode
+        let literal = PromptBuilder::new()
+            .output("Reply as {decision}: {reason}")
+            .build();
+        assert_eq!(
+            literal.render_with(&[]).unwrap(),
+            "Output: Reply as {decision}: {reason}"
+        );
+    }
+
+    #[test]
+    fn test_with_guardrails() {
+        let prompt = PromptBuilder::new()
+            .goal("Explain the overdraft fee")
+            .build()
+            .with_guardrails(GuardrailSet::CustomerFacing);
+
+        let text = prompt.to_string();
+        assert!(text.starts_with(&format!("Constraint: {}", guardrails::NO_FABRICATION)));
+        assert!(text.contains(guardrails::NO_GUARANTEES));
+        assert!(text.ends_with("Goal: Explain the overdraft fee"));
+    }
+
+    #[test]
+    fn test_equivalent_ignoring_case_and_whitespace() {
+        let a = PromptBuilder::new()
+            .goal("Review the  loan application")
+            .step("Check DTI ratio")
+            .build();
+        let b = PromptBuilder::new()
+            .goal("  review THE loan\napplication ")
+            .step("check   dti ratio")
+            .build();
+
+        assert!(a.is_equivalent_to(&b));
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn test_not_equivalent_on_content_or_kind() {
+        let a = PromptBuilder::new().goal("Review loan").build();
+
+        assert!(!a.is_equivalent_to(&PromptBuilder::new().goal("Review lease").build()));
+        assert!(!a.is_equivalent_to(&PromptBuilder::new().step("Review loan").build()));
+        assert!(!a.is_equivalent_to(&Prompt::new()));
+    }
+
+    #[test]
+    fn test_canonicalize_is_deterministic() {
+        let build = || {
+            PromptBuilder::new()
+                .goal("Review {account} for {customer}")
+                .slot("customer")
+                .slot("account")
+                .step("Check balance")
+                .build()
+        };
+
+        let first = build().canonicalize();
+        let second = build().canonicalize();
+
+        assert_eq!(first.as_bytes(), second.as_bytes());
+        assert!(first.starts_with(r#"{"required_slots":["customer","account"],"sections":"#));
+        assert_eq!(build().checksum(), build().checksum());
+        assert_ne!(build().checksum(), Prompt::new().checksum());
+    }
+
+    #[test]
+    fn test_normalize_order() {
+        let scrambled = PromptBuilder::new()
+            .output("Decision")
+            .step("First step")
+            .constraint("No PII")
+            .goal("Review claim")
+            .step("Second step")
+            .role("Claims Analyst")
+            .build();
+
+        assert_eq!(
+            scrambled.normalize_order().to_string(),
+            "Role: Claims Analyst\nGoal: Review claim\nStep: First step\nStep: Second step\nConstraint: No PII\nOutput: Decision"
+        );
+    }
+
+    #[test]
+    fn test_prompt_delta_undo() {
+        let original = PromptBuilder::new()
+            .goal("Review loan")
+            .step("Check DTI ratio")
+            .build();
+        let mut edited = original.clone();
+
+        let edits = [
+            PromptDelta::AddSection {
+                index: 1,
+                section: PromptSection::Role("Underwriter".to_string()),
+            },
+            PromptDelta::ReplaceSection {
+                index: 0,
+                section: PromptSection::Goal("Review mortgage".to_string()),
+            },
+            PromptDelta::RemoveSection { index: 2 },
+        ];
+        let mut undo_stack = Vec::new();
+        for delta in edits {
+            undo_stack.push(edited.apply(delta).unwrap());
+        }
+        assert_eq!(
+            edited.to_string(),
+            "Goal: Review mortgage\nRole: Underwriter"
+        );
+
+        while let Some(inverse) = undo_stack.pop() {
+            edited.apply(inverse).unwrap();
+        }
+        assert_eq!(edited, original);
+        assert!(edited
+            .apply(PromptDelta::RemoveSection { index: 5 })
+            .is_err());
+    }
+
+    #[test]
+    fn test_estimated_tokens_with_custom_tokenizer() {
+        struct WordTokenizer;
+
+        impl Tokenizer for WordTokenizer {
+            fn count_tokens(&self, text: &str) -> usize {
+                text.split_whitespace().count()
+            }
+        }
+
+        let prompt = PromptBuilder::new()
+            .goal("Review loan")
+            .step("Check DTI ratio")
+            .build();
+
+        assert_eq!(prompt.estimated_tokens_with(&WordTokenizer), 7);
+        assert_eq!(
+            prompt.estimated_tokens_with(&HeuristicTokenizer),
+            prompt.estimated_tokens()
+        );
+    }
+
+    #[test]
+    fn test_complexity_score() {
+        let bare = PromptBuilder::new().goal("Summarize").build();
+        let rich = BankingTemplate::CreditRisk {
+            loan_type: "commercial real estate".to_string(),
+            focus: "debt service coverage".to_string(),
+        }
+        .to_builder()
+        .build();
+
+        assert!(rich.complexity_score() > bare.complexity_score());
+        assert!((0.0..=1.0).contains(&rich.complexity_score()));
+    }
+
+    #[test]
+    fn test_localized_labels() {
+        let prompt = PromptBuilder::new()
+            .goal("Évaluer le risque de crédit")
+            .role("Analyste crédit")
+            .avoid("promettre un taux")
+            .build();
+        let french = LabelSet {
+            goal: "Objectif".to_string(),
+            role: "Rôle".to_string(),
+            step: "Étape".to_string(),
+            output: "Sortie".to_string(),
+            constraint: "Contrainte".to_string(),
+            avoid: "À éviter".to_string(),
+            avoid_item: "- Ne pas : ".to_string(),
+            tone_example: "Exemple de ton".to_string(),
+            context: "Contexte".to_string(),
+        };
+
+        let text = prompt.to_string_with_labels(&french);
+
+        assert!(text.contains("Objectif: Évaluer le risque de crédit"));
+        assert!(text.contains("Rôle: Analyste crédit"));
+        assert!(text.contains("À éviter:\n- Ne pas : promettre un taux"));
+        assert!(!text.contains("Goal:"));
+        assert!(!text.contains("Do not"));
+        assert_eq!(
+            Prompt::parse_with(&text, &french, ParseMode::Strict).unwrap(),
+            prompt
+        );
+        assert_eq!(
+            prompt.to_string_with_labels(&LabelSet::default()),
+            prompt.to_string()
+        );
+    }
+
+    #[test]
+    fn test_separator_rendering() {
+        let prompt = PromptBuilder::new()
+            .goal("Review statement")
+            .role("Analyst")
+            .step("Check balances")
+            .output("Summary")
+            .build();
+
+        let text = prompt.to_string_with_separator("\n---\n");
+
+        assert_eq!(text.matches("\n---\n").count(), prompt.sections.len() - 1);
+        assert!(!text.ends_with("---\n"));
+        assert_eq!(prompt.to_string_with_separator("\n"), prompt.to_string());
+    }
+
+    #[test]
+    fn test_separator_rendering_groups_avoids() {
+        let prompt = PromptBuilder::new()
+            .goal("Review statement")
+            .avoid("Guessing balances")
+            .step("Check balances")
+            .avoid("Sharing account numbers")
+            .build();
+
+        let text = prompt.to_string_with_separator("\n---\n");
+
+        // Goal, the grouped Avoid block, and Step: three blocks, two separators
+        assert_eq!(text.matches("\n---\n").count(), 2);
+        assert!(text.contains("Guessing balances\n- Do not: Sharing account numbers\n---\n"));
+    }
+
+    #[test]
+    fn test_numbered_string() {
+        let prompt = PromptBuilder::new()
+            .goal("Review statement")
+            .step("Check balances\nacross all accounts")
+            .output("Summary")
+            .build();
+
+        let numbered = prompt.to_numbered_string();
+        let lines: Vec<&str> = numbered.lines().collect();
+
+        assert!(lines[0].starts_with("1: Goal: Review statement"));
+        assert_eq!(lines[2], "3: across all accounts");
+        assert_eq!(lines[3], "4: Output: Summary");
+    }
+
+    #[test]
+    fn test_explain() {
+        let prompt = PromptBuilder::new()
+            .goal("Review dispute")
+            .role("Disputes Analyst")
+            .step("Check transaction")
+            .step("Check merchant response")
+            .constraint("Follow Regulation E timelines")
+            .output("Decision letter")
+            .build();
+
+        let explanation = prompt.explain();
+
+        for section in &prompt.sections {
+            assert!(explanation.contains(section.purpose()));
+        }
+        assert!(explanation.contains("Goal — defines the objective the model optimizes for"));
+        assert_eq!(explanation.matches("↳ Step —").count(), 2);
+    }
+
+    #[test]
+    fn test_decision_codes() {
+        let prompt = PromptBuilder::new()
+            .goal("Decide on wire transfer")
+            .decision_codes(&["APPROVE", "HOLD", "DECLINE"])
+            .build();
+
+        let text = prompt.to_string();
+        assert!(text.contains("Output: Respond with exactly one of these decision codes"));
+        assert!(text.contains("APPROVE, HOLD, DECLINE"));
+    }
+
+    #[test]
+    fn test_avoid_grouping() {
+        let prompt = PromptBuilder::new()
+            .goal("Reply to customer")
+            .avoid("quote internal policy numbers")
+            .step("Apologize for the delay")
+            .avoid("promise a refund")
+            .build();
+
+        assert_eq!(
+            prompt.to_string(),
+            "Goal: Reply to customer\nAvoid:\n- Do not: quote internal policy numbers\n- Do not: promise a refund\nStep: Apologize for the delay"
+        );
+
+        let grouped = PromptBuilder::new()
+            .goal("Reply to customer")
+            .avoid("quote internal policy numbers")
+            .avoid("promise a refund")
+            .step("Apologize for the delay")
+            .build();
+        assert_eq!(Prompt::parse(&prompt.to_string()).unwrap(), grouped);
+    }
+
+    #[test]
+    fn test_tone_examples() {
+        let prompt = PromptBuilder::new()
+            .goal("Reply to a fee complaint")
+            .tone_example("We're sorry for the trouble and have refunded the fee.")
+            .tone_example("Thanks for your patience while we looked into this.")
+            .build();
+
+        let text = prompt.to_string();
+        assert!(text.contains(
+            "Tone Example: Match the tone and style of this example: We're sorry for the trouble and have refunded the fee."
+        ));
+        assert_eq!(text.matches("Tone Example:").count(), 2);
+        assert_eq!(Prompt::parse(&text).unwrap(), prompt);
+    }
+
+    #[test]
+    fn test_reference_docs() {
+        let prompt = PromptBuilder::new()
+            .goal("Answer the customer's question about wire fees")
+            .reference_doc(
+                "Fee Schedule 2026",
+                "Domestic wires: $25\nInternational wires: $45",
+            )
+            .reference_doc(
+                "Wire Cut-off Policy",
+                "Wires submitted after 4pm ET settle next day",
+            )
+            .step("Quote the applicable fee")
+            .build();
+
+        let text = prompt.to_string();
+        assert!(text.contains(
+            "Context: --- Reference: Fee Schedule 2026 ---\nDomestic wires: $25\nInternational wires: $45\n"
+        ));
+        assert!(text.contains(
+            "Context: --- Reference: Wire Cut-off Policy ---\nWires submitted after 4pm ET settle next day\n"
+        ));
+        assert_eq!(Prompt::parse(&text).unwrap(), prompt);
+    }
 
-// ═══════════════════════════════════════════════════════════════════════════════════
-// SECTION: Tests
-// ═══════════════════════════════════════════════════════════════════════════════════
+    #[test]
+    fn test_max_section_len_truncates() {
+        let prompt = PromptBuilder::new()
+            .with_max_section_len(10)
+            .goal("0123456789")
+            .step("0123456789X")
+            .build();
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        assert_eq!(prompt.to_string(), "Goal: 0123456789\nStep: 0123456789");
+    }
 
     #[test]
-    fn test_prompt_builder() {
+    fn test_max_section_len_errors() {
+        let at_limit = PromptBuilder::new()
+            .with_max_section_len(10)
+            .section_limit_mode(SectionLimitMode::Error)
+            .goal("0123456789")
+            .try_build();
+        assert!(at_limit.is_ok());
+
+        let over_limit = || {
+            PromptBuilder::new()
+                .with_max_section_len(10)
+                .section_limit_mode(SectionLimitMode::Error)
+                .goal("0123456789")
+                .step("0123456789X")
+        };
+        assert!(over_limit()
+            .try_build()
+            .unwrap_err()
+            .to_string()
+            .contains("11 characters, exceeding the limit of 10"));
+        // build() never lets an over-long section through
+        assert_eq!(
+            over_limit().build().to_string(),
+            "Goal: 0123456789\nStep: 0123456789"
+        );
+    }
+
+    #[test]
+    fn test_escalation_path() {
         let prompt = PromptBuilder::new()
-            .goal("Test goal")
-            .role("Test role")
-            .step("Test step")
+            .goal("Assess suspicious activity")
+            .escalation_path(&["Branch Manager", "BSA Officer", "Chief Compliance Officer"])
             .build();
 
         let text = prompt.to_string();
-        assert!(text.contains("Goal: Test goal"));
-        assert!(text.contains("Role: Test role"));
-        assert!(text.contains("Step: Test step"));
+        assert!(text.contains("Output: If risk is high, state the escalation path"));
+        assert!(text.contains("Branch Manager -> BSA Officer -> Chief Compliance Officer"));
+    }
+
+    #[test]
+    fn test_steps_from_str() {
+        let procedure = "1. Verify customer identity\n\n2) Review account history\n  - Escalate to compliance\n* File the report\n-5% fee waiver\n";
+        let prompt = PromptBuilder::new().steps_from_str(procedure).build();
+
+        let text = prompt.to_string();
+        assert_eq!(
+            text,
+            "Step: Verify customer identity\nStep: Review account history\nStep: Escalate to compliance\nStep: File the report\nStep: -5% fee waiver"
+        );
+    }
+
+    #[test]
+    fn test_summary() {
+        let prompt = PromptBuilder::new()
+            .role("Credit Analyst")
+            .goal("Evaluate loan application")
+            .build();
+        assert_eq!(prompt.summary(80), "Evaluate loan application");
+    }
+
+    #[test]
+    fn test_summary_empty_prompt() {
+        assert_eq!(Prompt::new().summary(80), "(empty prompt)");
+        assert_eq!(
+            PromptBuilder::new().goal("   ").build().summary(10),
+            "(empty prompt)"
+        );
+    }
+
+    #[test]
+    fn test_summary_truncation_boundary() {
+        let prompt = PromptBuilder::new().goal("Review wires").build();
+
+        assert_eq!(prompt.summary(12), "Review wires");
+        assert_eq!(prompt.summary(11), "Review wir…");
+        assert_eq!(prompt.summary(11).chars().count(), 11);
+    }
+
+    #[test]
+    fn test_summary_multiline_goal_marks_truncation() {
+        let prompt = PromptBuilder::new()
+            .goal("Line one of goal\nline two")
+            .build();
+
+        assert_eq!(prompt.summary(80), "Line one of goal…");
+        assert_eq!(prompt.summary(10), "Line one …");
+    }
+
+    #[test]
+    fn test_openai_function_call() {
+        let prompt = PromptBuilder::new()
+            .goal("Classify wire transfer risk")
+            .output("Risk level with justification")
+            .build();
+
+        let tool = prompt.to_openai_function_call();
+
+        assert_eq!(tool["type"], "function");
+        assert_eq!(tool["function"]["name"], "classify_wire_transfer_risk");
+        assert_eq!(
+            tool["function"]["description"],
+            "Classify wire transfer risk"
+        );
+        assert_eq!(tool["function"]["parameters"]["type"], "object");
+        assert_eq!(
+            tool["function"]["parameters"]["properties"]["result"]["description"],
+            "Risk level with justification"
+        );
+    }
+
+    #[test]
+    fn test_compress_keeps_punctuated_words() {
+        let prompt = PromptBuilder::new()
+            .goal("Review the file then check a.")
+            .step("a) Verify identity")
+            .build();
+
+        let text = prompt.compress().to_string();
+        assert!(text.contains("Review file then check a."));
+        assert!(text.contains("a) Verify identity"));
+    }
+
+    #[test]
+    fn test_compress() {
+        let prompt = PromptBuilder::new()
+            .goal("Please just review the very large wire of 250000 USD to Acme Holdings")
+            .step("Check the sanctions list and the beneficiary history")
+            .build();
+
+        let compressed = prompt.compress();
+        let text = compressed.to_string();
+
+        assert!(compressed.estimated_tokens() < prompt.estimated_tokens());
+        assert!(prompt.token_savings(&compressed) > 0);
+        assert!(text.contains("Please review large wire of 250000 USD to Acme Holdings"));
+        assert!(text.contains("sanctions list"));
+        assert!(!text.contains(" the "));
     }
 
     #[tokio::test]
@@ -324,6 +2920,376 @@ mod tests {
         assert!(!response.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_counting_client() {
+        let client = CountingClient::new(MockLLMClient);
+        assert_eq!(client.call_count(), 0);
+
+        client.generate("credit risk assessment").await.unwrap();
+        client.generate("credit risk assessment").await.unwrap();
+
+        assert_eq!(client.call_count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_wrapper_over_boxed_client() {
+        let client = CountingClient::new(boxed(MockLLMClient));
+
+        let response = client.generate("fraud check").await.unwrap();
+
+        assert!(response.contains("FRAUD"));
+        assert_eq!(client.call_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_truncating_client() {
+        let client = TruncatingClient::new(MockLLMClient, 40);
+
+        let response = client.generate("fraud check").await.unwrap();
+
+        assert!(response.chars().count() <= 40);
+        assert_eq!(response, "FRAUD ALERT ISSUED\n\nTransaction…");
+    }
+
+    #[tokio::test]
+    async fn test_preamble_client() {
+        let preamble = "You are the assistant of Example Bank. Follow all compliance policies.";
+        let client = PreambleClient::new(EchoClient, preamble);
+
+        let echoed = client
+            .generate("Goal: Explain wire cut-off times")
+            .await
+            .unwrap();
+
+        assert!(echoed.starts_with(preamble));
+        assert!(echoed.ends_with(&format!(
+            "{PREAMBLE_DELIMITER}Goal: Explain wire cut-off times"
+        )));
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_client() {
+        let dry_run = DryRunClient::new(MockLLMClient);
+
+        let report = dry_run.generate("Goal: Assess credit risk").await.unwrap();
+
+        assert!(report.starts_with("[DRY RUN]"));
+        assert!(report.contains("Model: mock-banking-llm"));
+        assert!(report.ends_with("Goal: Assess credit risk"));
+        assert!(!report.contains("CREDIT ANALYSIS COMPLETE"));
+    }
+
+    #[tokio::test]
+    async fn test_audit_client() {
+        let path = std::env::temp_dir().join(format!("audit-test-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let client = AuditClient::new(MockLLMClient, &path);
+
+        client.generate("credit risk review").await.unwrap();
+        client.generate("fraud review").await.unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let entries: Vec<AuditEntry> = contents
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].prompt, "credit risk review");
+        assert_eq!(entries[1].prompt, "fraud review");
+        assert!(entries[1].response.contains("FRAUD"));
+        assert_eq!(entries[0].checksum.len(), 16);
+        assert_eq!(entries[0].error, None);
+    }
+
+    #[tokio::test]
+    async fn test_audit_client_records_failures() {
+        let path =
+            std::env::temp_dir().join(format!("audit-failure-test-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let client = AuditClient::new(FailingClient, &path);
+
+        let err = client.generate("credit risk review").await.unwrap_err();
+        assert!(err.to_string().contains("backend unavailable"));
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let entry: AuditEntry = serde_json::from_str(contents.trim_end()).unwrap();
+
+        assert_eq!(entry.prompt, "credit risk review");
+        assert!(entry.response.is_empty());
+        assert_eq!(entry.error.as_deref(), Some("backend unavailable"));
+    }
+
+    #[tokio::test]
+    async fn test_ensemble_member_panic_is_isolated() {
+        struct PanickingClient;
+
+        #[async_trait]
+        impl SimpleLLMClient for PanickingClient {
+            async fn generate(&self, _prompt: &str) -> Result<String> {
+                panic!("member crashed")
+            }
+        }
+
+        let ensemble = EnsembleClient::new(AggregationStrategy::FirstNonEmpty)
+            .with_client("crashing", PanickingClient, 1.0)
+            .with_client("steady", FixedClient("Approve"), 1.0);
+
+        assert_eq!(ensemble.generate("loan").await.unwrap(), "Approve");
+    }
+
+    #[tokio::test]
+    async fn test_ensemble_drop_aborts_members() {
+        struct SlowFlagClient(Arc<std::sync::atomic::AtomicBool>);
+
+        #[async_trait]
+        impl SimpleLLMClient for SlowFlagClient {
+            async fn generate(&self, _prompt: &str) -> Result<String> {
+                tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+                self.0.store(true, Ordering::SeqCst);
+                Ok("finished".to_string())
+            }
+        }
+
+        let finished = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let ensemble = EnsembleClient::new(AggregationStrategy::Majority).with_client(
+            "slow",
+            SlowFlagClient(Arc::clone(&finished)),
+            1.0,
+        );
+
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+        assert!(ensemble.generate_cancellable("loan", cancel).await.is_err());
+
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+        assert!(!finished.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_ensemble_majority() {
+        let ensemble = EnsembleClient::new(AggregationStrategy::Majority)
+            .with_client("outlier", FixedClient("Transaction looks legitimate."), 1.0)
+            .with_client("mock-a", MockLLMClient, 1.0)
+            .with_client("mock-b", MockLLMClient, 1.0);
+
+        let response = ensemble.generate("fraud review").await.unwrap();
+
+        assert!(response.starts_with("FRAUD ALERT ISSUED"));
+    }
+
+    #[tokio::test]
+    async fn test_mock_embedding_client() {
+        let client = MockEmbeddingClient;
+
+        let first = client.embed("card-not-present fraud").await.unwrap();
+        let again = client.embed("card-not-present fraud").await.unwrap();
+        let other = client.embed("wire fraud").await.unwrap();
+
+        assert_eq!(first.len(), MockEmbeddingClient::DIMENSIONS);
+        assert_eq!(first, again);
+        assert_ne!(first, other);
+    }
+
+    #[test]
+    fn test_wrap_with_attestation() {
+        let wrapped = wrap_with_attestation("Loan approved.", "J. Rivera, Credit Officer");
+
+        let block = wrapped.find("--- Compliance Attestation ---").unwrap();
+        assert!(wrapped.starts_with("Loan approved."));
+        assert!(wrapped[block..].contains("Attested by: J. Rivera, Credit Officer"));
+        assert!(wrapped[block..].contains(TIMESTAMP_PLACEHOLDER));
+    }
+
+    #[tokio::test]
+    async fn test_generate_cancellable() {
+        struct SlowClient;
+
+        #[async_trait]
+        impl SimpleLLMClient for SlowClient {
+            async fn generate(&self, _prompt: &str) -> Result<String> {
+                tokio::time::sleep(tokio::time::Duration::from_secs(30)).await;
+                Ok("too late".to_string())
+            }
+        }
+
+        let cancel = CancellationToken::new();
+        let trigger = cancel.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+            trigger.cancel();
+        });
+
+        let err = SlowClient
+            .generate_cancellable("credit risk", cancel)
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.to_string(), "generation cancelled");
+    }
+
+    #[tokio::test]
+    async fn test_seeded_generation() {
+        let client = boxed(MockLLMClient);
+        let seeded = |seed| GenOptions {
+            seed: Some(seed),
+            ..GenOptions::default()
+        };
+
+        let first = client
+            .generate_with_options("credit risk", seeded(7))
+            .await
+            .unwrap();
+        let again = client
+            .generate_with_options("credit risk", seeded(7))
+            .await
+            .unwrap();
+        let other = client
+            .generate_with_options("credit risk", seeded(8))
+            .await
+            .unwrap();
+
+        assert_eq!(first, again);
+        assert_ne!(first, other);
+        assert_eq!(
+            FixedClient("plain")
+                .generate_with_options("credit risk", seeded(7))
+                .await
+                .unwrap(),
+            "plain"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_seed_survives_wrappers() {
+        let audit_path =
+            std::env::temp_dir().join(format!("audit-seed-test-{}.jsonl", std::process::id()));
+        let seeded = |seed| GenOptions {
+            seed: Some(seed),
+            ..GenOptions::default()
+        };
+        let wrappers: Vec<Box<dyn SimpleLLMClient>> = vec![
+            boxed(CountingClient::new(MockLLMClient)),
+            boxed(TruncatingClient::new(MockLLMClient, 10_000)),
+            boxed(AuditClient::new(MockLLMClient, &audit_path)),
+            boxed(PreambleClient::new(MockLLMClient, "Follow bank policy.")),
+            boxed(
+                EnsembleClient::new(AggregationStrategy::FirstNonEmpty).with_client(
+                    "mock",
+                    MockLLMClient,
+                    1.0,
+                ),
+            ),
+        ];
+
+        for client in &wrappers {
+            let first = client
+                .generate_with_options("credit risk", seeded(7))
+                .await
+                .unwrap();
+            let other = client
+                .generate_with_options("credit risk", seeded(8))
+                .await
+                .unwrap();
+            assert_ne!(first, other);
+        }
+
+        std::fs::remove_file(&audit_path).unwrap();
+
+        let counting = CountingClient::new(MockLLMClient);
+        counting
+            .generate_with_options("credit risk", seeded(7))
+            .await
+            .unwrap();
+        assert_eq!(counting.call_count(), 1);
+
+        let report = DryRunClient::new(MockLLMClient)
+            .generate_with_options("credit risk", seeded(7))
+            .await
+            .unwrap();
+        assert!(report.contains("seed: Some(7)"));
+    }
+
+    #[tokio::test]
+    async fn test_batch_result_summary() {
+        struct FlakyClient;
+
+        #[async_trait]
+        impl SimpleLLMClient for FlakyClient {
+            async fn generate(&self, prompt: &str) -> Result<String> {
+                if prompt.contains("timeout") {
+                    anyhow::bail!("upstream timeout");
+                }
+                Ok(format!("done: {prompt}"))
+            }
+        }
+
+        let batch = FlakyClient
+            .generate_batch(&["loan 1", "timeout 2", "loan 3", "loan 4"])
+            .await;
+
+        assert!((batch.success_rate() - 0.75).abs() < f64::EPSILON);
+        assert_eq!(
+            batch.successes(),
+            vec!["done: loan 1", "done: loan 3", "done: loan 4"]
+        );
+        let failures = batch.failures();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].0, 1);
+        assert_eq!(failures[0].1.to_string(), "upstream timeout");
+        assert!(batch.into_results()[1].is_err());
+    }
+
+    #[tokio::test]
+    async fn test_health_check() {
+        assert!(MockLLMClient.health_check().await.is_ok());
+        assert!(boxed(FailingClient).health_check().await.is_err());
+        assert!(FixedClient("   ").health_check().await.is_err());
+    }
+
+    #[test]
+    fn test_mock_model_info() {
+        let info = boxed(MockLLMClient).model_info();
+
+        assert_eq!(info.max_context_tokens, MockLLMClient::MAX_CONTEXT_TOKENS);
+        assert_eq!(info.name, "mock-banking-llm");
+        assert_eq!(FixedClient("ok").model_info(), ModelInfo::unknown());
+    }
+
+    #[test]
+    fn test_truncate_to() {
+        let prompt = PromptBuilder::new()
+            .goal("Review")
+            .step("Check the full transaction history for the last twelve months")
+            .build();
+
+        assert_eq!(prompt.truncate_to(1000).to_string(), prompt.to_string());
+        assert_eq!(prompt.truncate_to(5).to_string(), "Goal: Review");
+    }
+
+    #[tokio::test]
+    async fn test_capture_test_case_round_trip() {
+        let prompt = BankingTemplate::FraudDetection {
+            channel: "mobile app".to_string(),
+            scope: "device fingerprinting".to_string(),
+        }
+        .to_builder()
+        .build();
+        let response = MockLLMClient.generate(&prompt.to_string()).await.unwrap();
+
+        let fixture = load_test_case(&capture_test_case(&prompt, &response)).unwrap();
+
+        assert_eq!(fixture.prompt, prompt);
+        assert_eq!(fixture.rendered, prompt.to_string());
+        assert_eq!(fixture.response, response);
+        assert_eq!(
+            MockLLMClient.generate(&fixture.rendered).await.unwrap(),
+            fixture.response
+        );
+    }
+
     #[test]
     fn test_credit_risk_template() {
         let template = BankingTemplate::CreditRisk {
@@ -354,6 +3320,44 @@ mod tests {
         assert!(text.contains("real-time"));
     }
 
+    #[test]
+    fn test_template_with_citations() {
+        let template = BankingTemplate::CreditRisk {
+            loan_type: "auto loan".to_string(),
+            focus: "adverse action".to_string(),
+        };
+
+        let prompt = template
+            .to_builder_with_citations(&[Regulation::Ecoa, Regulation::Fcra])
+            .build();
+
+        let constraint = prompt
+            .to_string()
+            .lines()
+            .find(|line| line.starts_with("Constraint:"))
+            .unwrap()
+            .to_string();
+        assert!(constraint.contains("ECOA"));
+        assert!(constraint.contains("FCRA"));
+        assert!(!constraint.contains("BSA"));
+    }
+
+    #[test]
+    fn test_chain_of_thought_toggle() {
+        let template = BankingTemplate::FraudDetection {
+            channel: "ACH transfers".to_string(),
+            scope: "account takeover signals".to_string(),
+        };
+
+        let with_cot = template.to_builder_with_chain_of_thought(true).build();
+        let without_cot = template.to_builder_with_chain_of_thought(false).build();
+
+        assert!(with_cot.to_string().contains("Think step by step"));
+        assert!(with_cot.to_string().contains("Conclusion:"));
+        assert!(!without_cot.to_string().contains("Think step by step"));
+        assert_eq!(without_cot, template.to_builder().build());
+    }
+
     #[test]
     fn test_template_descriptions() {
         let credit_template = BankingTemplate::CreditRisk {
@@ -369,4 +3373,125 @@ mod tests {
         assert!(credit_template.description().contains("mortgage"));
         assert!(fraud_template.description().contains("credit cards"));
     }
+
+    #[test]
+    fn test_portfolio_review_template() {
+        let template = BankingTemplate::PortfolioReview {
+            client_profile: "high-net-worth retiree".to_string(),
+            objective: "capital preservation".to_string(),
+        };
+
+        let text = template.to_builder().build().to_string();
+
+        assert!(text.contains("capital preservation"));
+        assert!(text.contains("Wealth Advisor"));
+        assert!(template.description().contains("capital preservation"));
+    }
+
+    #[test]
+    fn test_cfpb_narrative_template() {
+        let template = BankingTemplate::CfpbNarrative {
+            complaint_type: "overdraft fee dispute".to_string(),
+            outcome: "fee refund".to_string(),
+        };
+
+        let text = template.to_builder().build().to_string();
+
+        assert!(text.contains("overdraft fee dispute"));
+        assert!(text.contains("Regulatory Response Analyst"));
+        assert!(template.description().contains("overdraft fee dispute"));
+    }
+
+    #[test]
+    fn test_trade_finance_template() {
+        let template = BankingTemplate::TradeFinance {
+            instrument: "documentary letter of credit".to_string(),
+            discrepancy_focus: "bill of lading dates".to_string(),
+        };
+
+        let text = template.to_builder().build().to_string();
+
+        assert!(text.contains("documentary letter of credit"));
+        assert!(text.contains("UCP 600"));
+        assert!(template
+            .description()
+            .contains("documentary letter of credit"));
+    }
+
+    #[test]
+    fn test_p2p_scam_template() {
+        let template = BankingTemplate::P2pScam {
+            scam_type: "impersonation".to_string(),
+            signals: "new payee and active phone call".to_string(),
+        };
+
+        let text = template.to_builder().build().to_string();
+
+        assert!(text.contains("impersonation"));
+        assert!(text.contains("Fraud Analyst"));
+        assert!(template.description().contains("impersonation"));
+    }
+
+    #[test]
+    fn test_rcsa_template() {
+        let template = BankingTemplate::Rcsa {
+            process: "wire transfer release".to_string(),
+            risk_category: "execution".to_string(),
+        };
+
+        let text = template.to_builder().build().to_string();
+
+        assert!(text.contains("wire transfer release"));
+        assert!(text.contains("Operational Risk Analyst"));
+        assert!(template.description().contains("wire transfer release"));
+    }
+
+    #[test]
+    fn test_cash_structuring_template() {
+        let template = BankingTemplate::CashStructuring {
+            threshold: "$10,000".to_string(),
+            window: "a rolling 5-day window".to_string(),
+        };
+
+        let text = template.to_builder().build().to_string();
+
+        assert!(text.contains("$10,000"));
+        assert!(text.contains("BSA Analyst"));
+        assert!(template.description().contains("$10,000"));
+    }
+
+    #[test]
+    fn test_complaint_resolution_template() {
+        let template = BankingTemplate::ComplaintResolution {
+            product: "checking account".to_string(),
+            severity: "high".to_string(),
+        };
+
+        let text = template.to_builder().build().to_string();
+
+        assert!(text.contains("checking account"));
+        assert!(text.contains("high severity"));
+        assert!(text.contains("Empathetic Customer Support Specialist"));
+        assert!(template.description().contains("checking account"));
+        assert!(template.description().contains("high"));
+    }
+
+    #[test]
+    fn test_statement_analysis_template() {
+        let template = BankingTemplate::StatementAnalysis {
+            entity_type: "manufacturing company".to_string(),
+            period: "FY2023-FY2025".to_string(),
+        };
+
+        let prompt = template.to_builder().build();
+        let text = prompt.to_string();
+
+        assert!(text.contains("manufacturing company"));
+        assert!(text.contains("FY2023-FY2025"));
+        assert!(text
+            .lines()
+            .filter(|line| line.starts_with("Step:"))
+            .all(|step| step.contains("ratio")));
+        assert!(template.description().contains("manufacturing company"));
+    }
 }